@@ -90,7 +90,7 @@ impl UnhandledEvent {
     self.is_ms_ago(1000 / fps)
   }
 
-  pub fn event_as_line<I>(&self, spans: impl Into<Option<I>>) -> Line
+  pub fn event_as_line<I>(&self, spans: impl Into<Option<I>>) -> Line<'_>
   where
     I: IntoIterator<Item = Span<'static>>,
   {
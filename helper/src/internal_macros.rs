@@ -73,7 +73,7 @@ macro_rules! __keys_with_mods_kind {
 /// 4. KeyCode alternatives: keys!(Char('s') | Char('g'))
 ///
 /// # Examples:
-/// ```no_run
+/// ```ignore
 /// xterm_defined::keys!(Enter);                        // Just keycode
 /// xterm_defined::keys!(Char('a'));                    // Keycode with value
 /// xterm_defined::keys!(F(1));                         // Function key
@@ -1,7 +1,7 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crossterm::event::{Event, KeyEvent, MouseEvent};
-use helper::{UnhandledEvent, keys};
+use crossterm::event::{Event, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use helper::UnhandledEvent;
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Alignment, Constraint, Layout, Rect};
 use ratatui::prelude::Widget;
@@ -9,36 +9,74 @@ use ratatui::widgets::{Paragraph, Wrap};
 use tokio::task::JoinSet;
 
 use super::*;
-use crate::ui::blk;
-use crate::widgets::{Alert, Input, Logs, Statistic};
+use crate::clipboard::{self, Copied};
+use crate::config::{self, Action, Chord, Cli, Config, Context, Dispatch};
+use crate::mouse_area;
+use crate::ui::{blk, paint_with};
+use crate::widgets::{
+  Alert, Breakdown, Confirmation, Details, FilePicker, Input, Log, Logs, PathCompleter, PickMode, ReadableFile, Results, SearchQuery, Severity,
+  Statistic, Toasts, Validator, WritableFile, help, hints,
+};
 
 mod impls;
 
-pub enum InOutChangeMode {
-  Input,
-  Output,
+/// What the result of a popup layer is for.
+pub enum Intent {
+  /// Informational, nothing happens once it's closed.
+  Dismiss,
+  ChangeInput,
+  ChangeOutput,
+  /// Applied on every keystroke, `previous` comes back on cancel.
+  Search {
+    previous: String,
+  },
+  Export(Pane),
+  /// Yes replaces the existing file, no leaves it alone.
+  OverwriteExport(Pane, PathBuf),
+  /// Yes truncates the existing file, no appends to it.
+  OverwriteOutput(PathBuf),
+  ExitWhileProcessing,
+  RunSettings,
+}
+
+/// Bottom pane receiving navigation keys.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Pane {
+  #[default]
+  Activities,
+  Results,
 }
 
 pub struct App {
   state: State,
-  popup: Option<Popup>,
+  popups: PopupStack<Intent>,
 
   // App data
   input: Option<PathBuf>,
   output: Option<PathBuf>,
-  change_mode: Option<InOutChangeMode>,
 
   // bg task
   #[allow(dead_code)]
   tasks: JoinSet<()>,
   event_watcher: WatchTx<UnhandledEvent>,
+  /// Results of the popup jobs running on their own tasks, by ticket.
+  done_tx: mpsc::UnboundedSender<(u64, Done)>,
+  done_rx: mpsc::UnboundedReceiver<(u64, Done)>,
   pub output_tx: WatchTx<PathBuf>,
   pub input_tx: WatchTx<PathBuf>,
   pub state_tx: WatchTx<State>,
+  pub settings_tx: WatchTx<Settings>,
   focus: bool,
   scrols: ScrollStates,
   logs: Logs,
+  results: Results,
+  pane: Pane,
   statistic: Statistic,
+  breakdown: Breakdown,
+  toasts: Toasts,
+  cli: Cli,
+  /// Keys typed so far of a multi-key binding.
+  chord: Chord,
 }
 
 impl App {
@@ -46,6 +84,35 @@ impl App {
     self.state == State::Exit
   }
 
+  /// Drops popups whose timeout ran out and brings their jobs up to date, called before every draw.
+  pub fn tick(&mut self) {
+    self.popups.expire();
+    self.run_jobs();
+  }
+
+  /// Hands back the job results that came in and starts the jobs the top popup is missing.
+  /// A finished job asks for a draw, which lands here again.
+  fn run_jobs(&mut self) {
+    while let Ok((ticket, done)) = self.done_rx.try_recv() {
+      self.popups.deliver(ticket, done);
+    }
+    let Some(popup) = self.popups.top_mut() else {
+      return;
+    };
+    for Job { ticket, done } in popup.jobs() {
+      let done_tx = self.done_tx.clone();
+      let event = self.event_watcher.clone();
+      tokio::spawn(async move {
+        _ = done_tx.send((ticket, done.await));
+        event.send_modify(|e| *e = UnhandledEvent::render());
+      });
+    }
+  }
+
+  fn notify(&mut self, popup: Popup) {
+    self.popups.push(popup, Intent::Dismiss);
+  }
+
   pub fn subscribe_event(&self) -> WatchRx<UnhandledEvent> {
     self.event_watcher.subscribe()
   }
@@ -54,99 +121,368 @@ impl App {
     if let Some(handle) = self.logs.handle_mouse(mouse).await {
       return handle;
     }
+    if let Some(handle) = self.results.handle_mouse(mouse).await {
+      return handle;
+    }
 
     UnhandledEvent::no_ops()
   }
 
-  pub fn change_output(&mut self, input: Input) -> UnhandledEvent {
-    let output_file = PathBuf::from(input.value());
+  /// Context of the focused pane, popups only leave the global one.
+  fn context(&self) -> Context {
+    match self.popups.is_empty() {
+      true => self.pane_context(),
+      false => Context::Global,
+    }
+  }
+
+  fn pane_context(&self) -> Context {
+    match self.pane {
+      Pane::Activities => Context::Logs,
+      Pane::Results => Context::Results,
+    }
+  }
+
+  /// Actions of the panes context, then of the focused pane's own one.
+  async fn perform_pane(&mut self, action: Action) -> Option<UnhandledEvent> {
+    match (self.pane, action) {
+      (_, Action::SwitchPane) => {
+        self.pane = match self.pane {
+          Pane::Activities => Pane::Results,
+          Pane::Results => Pane::Activities,
+        };
+        Some(UnhandledEvent::render())
+      }
+      (Pane::Activities, Action::Search) => {
+        let input = Input::new(" Search logs, prefix with re: for a regex: ".to_string(), "Type to filter...".to_string())
+          .history("search")
+          .validator(SearchQuery);
+        let previous = self.logs.query().await;
+        self.popups.push(Popup::Input(Box::new(input)), Intent::Search { previous });
+        Some(UnhandledEvent::render())
+      }
+      (pane, Action::Export) => {
+        let label = match pane {
+          Pane::Activities => " Export shown activities to (.txt, .json, .md): ",
+          Pane::Results => " Export shown results to (.txt, .json, .md): ",
+        };
+        let input = Input::new(label.to_string(), "Start typing...".to_string())
+          .completer(PathCompleter::default())
+          .history("export")
+          .validator(WritableFile);
+        self.popups.push(Popup::Input(Box::new(input)), Intent::Export(pane));
+        Some(UnhandledEvent::render())
+      }
+      (_, Action::CopyNames) => {
+        let names = self.results.available_names().await;
+        let what = format!("{} available names", names.len());
+        Some(self.copy(&what, names.join("\n")).await)
+      }
+      (Pane::Activities, Action::CopySelected) => {
+        let text = self.logs.selected_text().await?;
+        Some(self.copy("the selected activity", text).await)
+      }
+      (Pane::Results, Action::CopySelected) => {
+        let bucket = self.results.selected().await?;
+        Some(self.copy("the selected bucket name", bucket.name().to_string()).await)
+      }
+      (Pane::Activities, Action::Details) => {
+        let details = self.logs.details().await?;
+        self.notify(Popup::Details(details));
+        Some(UnhandledEvent::render())
+      }
+      (Pane::Activities, action) => self.logs.perform(action).await,
+      (Pane::Results, Action::Details) => {
+        let bucket = self.results.selected().await?;
+        self.notify(Popup::Details(Details::from(&bucket)));
+        Some(UnhandledEvent::render())
+      }
+      (Pane::Results, action) => self.results.perform(action).await,
+    }
+  }
+
+  /// Keys outside the keymap, the digits of the focused pane.
+  async fn handle_pane_key(&mut self, key: KeyEvent) -> Option<UnhandledEvent> {
+    match self.pane {
+      Pane::Activities => self.logs.handle_key(key).await,
+      Pane::Results => self.results.handle_key(key).await,
+    }
+  }
+
+  pub fn change_output(&mut self, output_file: PathBuf) -> UnhandledEvent {
+    if output_file.metadata().is_ok_and(|metadata| metadata.is_file() && metadata.len() > 0) {
+      let prompt = format!("{} already exists. Overwrite it? No appends to it.", output_file.display());
+      self.confirm(prompt, Intent::OverwriteOutput(output_file));
+      return UnhandledEvent::render();
+    }
+
+    self.set_output(output_file);
+    UnhandledEvent::render()
+  }
+
+  fn set_output(&mut self, output_file: PathBuf) {
     self.output = Some(output_file);
     self.output_tx.send_modify(|current| *current = self.output.clone().unwrap());
+  }
+
+  fn confirm(&mut self, prompt: String, intent: Intent) {
+    self.popups.push(Popup::Confirmation(Confirmation::new(prompt, false)), intent);
+  }
+
+  async fn confirmed(&mut self, intent: Intent, answer: bool) -> UnhandledEvent {
+    match intent {
+      Intent::OverwriteOutput(output_file) => {
+        if answer && let Err(err) = tokio::fs::File::create(&output_file).await {
+          let alert = [err.to_string(), format!("File {}.", output_file.display())];
+          self.notify(Popup::error("Can't Overwrite Output", alert.into_iter()));
+          return UnhandledEvent::render();
+        }
+        self.set_output(output_file);
+      }
+      Intent::OverwriteExport(pane, path) if answer => return self.write_export(pane, path).await,
+      Intent::ExitWhileProcessing if answer => self.exit_now(),
+      _ => {}
+    }
 
     UnhandledEvent::render()
   }
 
-  pub async fn change_input(&mut self, input: Input) -> UnhandledEvent {
-    let input_file = PathBuf::from(input.value());
-    if !input_file.exists() {
-      let alert = ["File does not exists.".to_string(), format!("File {}.", input_file.display())];
-      let alert = Alert::new("Invalid Input", alert.into_iter());
-      self.popup = Some(Popup::Alert(alert));
+  /// Runs what the popup was opened for, once it has been submitted.
+  async fn resolve(&mut self, popup: Popup, intent: Intent) -> UnhandledEvent {
+    match (popup, intent) {
+      (Popup::FilePicker(picker), Intent::ChangeInput) => match picker.value() {
+        Some(input_file) => self.change_input(input_file).await,
+        None => UnhandledEvent::render(),
+      },
+      (Popup::FilePicker(picker), Intent::ChangeOutput) => match picker.value() {
+        Some(output_file) => self.change_output(output_file),
+        None => UnhandledEvent::render(),
+      },
+      (Popup::Input(input), Intent::Search { previous }) => self.search(*input, previous).await,
+      (Popup::Input(input), Intent::Export(pane)) => self.export(pane, *input).await,
+      (Popup::Form(form), Intent::RunSettings) => {
+        self.settings_tx.send_modify(|settings| settings.update(&form));
+        self.toasts.info("Run settings applied");
+        UnhandledEvent::render()
+      }
+      (Popup::Confirmation(confirmation), intent) => self.confirmed(intent, confirmation.value()).await,
+      _ => UnhandledEvent::render(),
+    }
+  }
+
+  async fn dismiss(&mut self, intent: Intent) -> UnhandledEvent {
+    if let Intent::Search { previous } = intent {
+      _ = self.logs.search(&previous).await;
+    }
+
+    UnhandledEvent::render()
+  }
+
+  fn exit_now(&mut self) {
+    self.state = State::Exit;
+    self.state_tx.send_modify(|current| *current = self.state);
+  }
+
+  async fn copy(&mut self, what: &str, text: String) -> UnhandledEvent {
+    let log = match clipboard::copy(&text) {
+      Ok(Copied::Terminal) => {
+        self.toasts.info(format!("Copied {what}"));
+        Log::info(format!("Copied {what} to the clipboard"))
+      }
+      Ok(Copied::File(path)) => {
+        let warn = format!("Clipboard unavailable, {what} written to {}", path.display());
+        self.toasts.warn(warn.clone());
+        Log::warn(warn)
+      }
+      Err(err) => {
+        self.toasts.error(format!("Copy failed: {err}"));
+        Log::error(err)
+      }
+    };
+    self.logs.add(log).await;
+
+    UnhandledEvent::render()
+  }
+
+  /// An invalid regex keeps the prompt open under the warning.
+  pub async fn search(&mut self, input: Input, previous: String) -> UnhandledEvent {
+    if let Err(error) = self.logs.search(input.text()).await {
+      let alert = ["Invalid regex.".to_string(), error.to_string()];
+      self.popups.push(Popup::Input(Box::new(input)), Intent::Search { previous });
+      self.notify(Popup::warning("Invalid Search", alert.into_iter()));
+    }
+
+    UnhandledEvent::render()
+  }
+
+  /// An existing file is only replaced once confirmed.
+  pub async fn export(&mut self, pane: Pane, input: Input) -> UnhandledEvent {
+    let path = PathBuf::from(input.value());
+    if tokio::fs::metadata(&path).await.is_ok_and(|metadata| metadata.is_file()) {
+      self.confirm(
+        format!("{} already exists. Overwrite it?", path.display()),
+        Intent::OverwriteExport(pane, path),
+      );
       return UnhandledEvent::render();
     }
-    if !input_file.is_file() {
-      let alert = ["Input is not a file.".to_string(), format!("File {}.", input_file.display())];
-      let alert = Alert::new("Invalid Input", alert.into_iter());
-      self.popup = Some(Popup::Alert(alert));
+
+    self.write_export(pane, path).await
+  }
+
+  async fn write_export(&mut self, pane: Pane, path: PathBuf) -> UnhandledEvent {
+    let export = match pane {
+      Pane::Activities => self.logs.export().await,
+      Pane::Results => self.results.export().await,
+    };
+
+    let alert = match export.write(&path).await {
+      Ok(rows) => {
+        let content = [format!("Wrote {rows} rows."), format!("File {}.", path.display())];
+        Popup::Alert(Alert::new("Exported", content.into_iter()).severity(Severity::Info))
+      }
+      Err(err) => Popup::error("Export Failed", [err.to_string(), format!("File {}.", path.display())].into_iter()),
+    };
+    self.notify(alert);
+
+    UnhandledEvent::render()
+  }
+
+  /// An invalid path reopens the picker under the warning so another file can be chosen.
+  pub async fn change_input(&mut self, input_file: PathBuf) -> UnhandledEvent {
+    if let Err(problem) = ReadableFile.validate(&input_file.to_string_lossy()).await {
+      let alert = [problem, format!("File {}.", input_file.display())];
+      self
+        .popups
+        .push(Popup::FilePicker(self.picker(PickMode::Open, &input_file)), Intent::ChangeInput);
+      self.notify(Popup::warning("Invalid Input", alert.into_iter()));
       return UnhandledEvent::render();
     }
 
+    self.breakdown.reset();
+    self.results.reset().await;
     self.input_tx.send_modify(|watch_path| *watch_path = input_file.clone());
     self.input = Some(input_file);
 
     UnhandledEvent::render()
   }
 
-  pub async fn handle_key(&mut self, key: KeyEvent) -> Option<UnhandledEvent> {
-    match key {
-      keys!(Char('i'), NONE, Press) => {
-        let input = Input::new(" Enter full/relative path to file input: ".to_string(), "Start typing...".to_string());
-        let popup = Popup::Input(input);
-        self.popup = Some(popup);
-        self.change_mode = Some(InOutChangeMode::Input);
+  /// Reads the configuration files again, flags and environment still apply on top.
+  /// Channel sizes only change on the next start.
+  pub async fn reload_config(&mut self) -> UnhandledEvent {
+    // Reading the files and the AWS ranges they point to is blocking work.
+    let cli = self.cli.clone();
+    let loaded = tokio::task::spawn_blocking(move || Config::load(&cli)).await;
+    let config = match loaded.map_err(anyhow::Error::from).and_then(|config| config) {
+      Ok(config) => config,
+      Err(err) => {
+        let alert = format!("{err:#}").lines().map(String::from).collect::<Vec<_>>();
+        self.notify(Popup::error("Configuration Not Reloaded", alert.into_iter()));
+        return UnhandledEvent::render();
+      }
+    };
+
+    let restart = config.channels != config::current().channels;
+    self.logs.reconfigure(&config.log).await;
+    self.settings_tx.send_modify(|settings| *settings = config.run.clone());
+    config::install(config);
+
+    self.logs.add(Log::info("Configuration reloaded")).await;
+    if restart {
+      self.logs.add(Log::warn("Channel sizes apply on the next start")).await;
+    }
+    self.toasts.info("Configuration reloaded");
+    UnhandledEvent::render()
+  }
+
+  /// Starts browsing next to `start`.
+  fn picker(&self, mode: PickMode, start: &Path) -> FilePicker {
+    let title = match mode {
+      PickMode::Open => "Choose input file",
+      PickMode::Save => "Choose output file",
+    };
+    FilePicker::new(title, mode, Some(start))
+  }
+
+  /// Actions of the global context.
+  pub async fn perform(&mut self, action: Action) -> Option<UnhandledEvent> {
+    match action {
+      Action::ChooseInput => {
+        let picker = self.picker(PickMode::Open, self.input.as_deref().unwrap_or(Path::new(".")));
+        self.popups.push(Popup::FilePicker(picker), Intent::ChangeInput);
         Some(UnhandledEvent::render())
       }
-      keys!(Char('o'), NONE, Press) => {
-        let input = Input::new(" Enter full/relative path to file output: ".to_string(), "Start typing...".to_string());
-        let popup = Popup::Input(input);
-        self.popup = Some(popup);
-        self.change_mode = Some(InOutChangeMode::Output);
+      Action::ChooseOutput => {
+        let picker = self.picker(PickMode::Save, self.output.as_deref().unwrap_or(Path::new(".")));
+        self.popups.push(Popup::FilePicker(picker), Intent::ChangeOutput);
         Some(UnhandledEvent::render())
       }
-      keys!(Char('s'), NONE, Press) => {
-        self.state = State::Processing;
-        self.state_tx.send_modify(|current| *current = self.state);
+      Action::ReloadConfig => Some(self.reload_config().await),
+      Action::RunSettings => {
+        let form = self.settings_tx.borrow().form();
+        self.popups.push(Popup::Form(form), Intent::RunSettings);
         Some(UnhandledEvent::render())
       }
-      keys!(Char('c'), CONTROL, Press) => {
-        self.state = State::Exit;
-        self.state_tx.send_modify(|current| *current = self.state);
+      Action::Start => {
+        // Resuming a running clock would restart its rate window.
+        if self.state.is_iddling() {
+          self.statistic.resume();
+          self.state = State::Processing;
+          self.state_tx.send_modify(|current| *current = self.state);
+        }
         Some(UnhandledEvent::render())
       }
-      keys!(Esc, NONE, Press) => {
+      Action::TogglePublicProbe => {
+        self.settings_tx.send_modify(|settings| settings.public_probe = !settings.public_probe);
+        let info = if self.settings_tx.borrow().public_probe {
+          "Public access probing enabled"
+        } else {
+          "Public access probing disabled"
+        };
+        self.logs.add(Log::info(info)).await;
+        Some(UnhandledEvent::render())
+      }
+      Action::Quit => {
+        if self.state.is_processing() {
+          self.confirm(String::from("Checks are still running. Exit anyway?"), Intent::ExitWhileProcessing);
+        } else {
+          self.exit_now();
+        }
+        Some(UnhandledEvent::render())
+      }
+      Action::Help => {
+        self.notify(Popup::Details(help(&config::current().keys, self.pane_context())));
+        Some(UnhandledEvent::render())
+      }
+      Action::Pause => {
+        self.statistic.pause();
         self.state = State::Iddling;
         self.state_tx.send_modify(|current| *current = self.state);
         Some(UnhandledEvent::render())
       }
-      KeyEvent { .. } => None,
+      _ => None,
     }
   }
 
   pub async fn handle(&mut self, event: Event) -> UnhandledEvent {
-    if let Some(popup) = self.popup.as_mut()
-      && let Some(handled) = popup.handle_event(&event)
-    {
-      if handled.kind.is_handled() {
-        match self.popup.take().unwrap() {
-          Popup::Input(input) => match self.change_mode {
-            None => {}
-            Some(ref mode) => {
-              return match mode {
-                InOutChangeMode::Input => self.change_input(input).await,
-                InOutChangeMode::Output => self.change_output(input),
-              };
-            }
-          },
-          Popup::Confirmation(_) => todo!(),
-          Popup::Warning(_) => todo!(),
-          Popup::Alert(_) => return UnhandledEvent::render(),
+    if let Some(popup) = self.popups.top_mut() {
+      // Submitting is refused until the verdict on the text as it is now comes back, see `run_jobs`.
+      if let Some(handled) = popup.handle_event(&event) {
+        if handled.kind.is_handled() {
+          let (popup, intent) = self.popups.pop().unwrap();
+          return self.resolve(popup, intent).await;
+        } else if handled.kind.is_canceled() {
+          let (_, intent) = self.popups.pop().unwrap();
+          return self.dismiss(intent).await;
+        } else {
+          if handled.kind.is_render()
+            && let Some((Popup::Input(input), Intent::Search { .. })) = self.popups.top()
+          {
+            // Half-typed regexes are expected, the last valid one stays until submit
+            let query = input.text().to_string();
+            _ = self.logs.search(&query).await;
+          }
+          return handled;
         }
-      } else if handled.kind.is_canceled() {
-        self.popup.take();
-
-        return UnhandledEvent::render();
-      } else {
-        return handled;
       }
     }
 
@@ -158,22 +494,41 @@ impl App {
         return UnhandledEvent::render();
         */
       }
-      Event::Key(key) => {
-        // First try to handle logs scrolling if no popup is active
-        if self.popup.is_none()
-          && let Some(handled) = self.logs.handle_key(key).await
-        {
-          return handled;
+      Event::Key(key) => match config::current().keys.dispatch(self.context(), &mut self.chord, key) {
+        Dispatch::Action(action) => {
+          // The focused pane gets the action first when no popup is active
+          if self.popups.is_empty()
+            && let Some(handled) = self.perform_pane(action).await
+          {
+            return handled;
+          }
+
+          if let Some(unhandled_event) = self.perform(action).await {
+            return unhandled_event;
+          }
         }
-
-        if let Some(unhandled_event) = self.handle_key(key).await {
-          return unhandled_event;
+        Dispatch::Pending => return UnhandledEvent::no_ops(),
+        Dispatch::Unbound => {
+          if self.popups.is_empty()
+            && let Some(handled) = self.handle_pane_key(key).await
+          {
+            return handled;
+          }
         }
-      }
+      },
       Event::Mouse(mouse) => {
-        if let Some(handled) = self.logs.handle_mouse(mouse).await {
-          return handled;
+        let position = mouse_area(&mouse).as_position();
+        if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+          if self.logs.intersects(position) {
+            self.pane = Pane::Activities;
+            return UnhandledEvent::render();
+          }
+          if self.results.intersects(position) {
+            self.pane = Pane::Results;
+            return UnhandledEvent::render();
+          }
         }
+        return self.handle_mouse(mouse).await;
       }
       Event::Paste(_) => {}
       Event::Resize(_, _) => return UnhandledEvent::render(),
@@ -219,8 +574,11 @@ impl Widget for &App {
     if !self.focus {
       return;
     }
+    let config = config::current();
+    paint_with(config.colors);
 
-    let [controls, activity] = Layout::vertical([Constraint::Percentage(30), Constraint::Percentage(70)]).areas(area);
+    let [main, hint] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+    let [controls, activity] = Layout::vertical([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(main);
     let controls = Layout::horizontal([Constraint::Percentage(68), Constraint::Fill(1)]).split(controls);
     let control_chunks = Layout::vertical([Constraint::Length(3), Constraint::Length(3), Constraint::Fill(1)]).split(controls[0]);
 
@@ -228,14 +586,18 @@ impl Widget for &App {
       self.draw_input_widget().render(control_chunks[0], buf);
       self.draw_output_widget().render(control_chunks[1], buf);
 
-      // Render statistic widget in the right column
-      self.statistic.render(controls[1], buf);
+      // Statistic and breakdown share the right column
+      let [statistic, breakdown] = Layout::vertical([Constraint::Length(Statistic::HEIGHT), Constraint::Fill(1)]).areas(controls[1]);
+      self.statistic.render(statistic, buf);
+      self.breakdown.render(breakdown, buf);
 
-      self.logs.render(activity, buf);
+      let [logs, results] = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(activity);
+      self.logs.render_focused(logs, buf, self.pane == Pane::Activities);
+      self.results.render_focused(results, buf, self.pane == Pane::Results);
+      hints(&config.keys, self.pane_context(), self.state.is_processing()).render(hint, buf);
 
-      if let Some(ref popup) = self.popup {
-        popup.render(area, buf)
-      }
+      self.popups.render(area, buf);
+      self.toasts.render(area, buf);
     });
   }
 }
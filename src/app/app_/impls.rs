@@ -1,22 +1,36 @@
 use helper::UnhandledEvent;
 
 use super::*;
-use crate::app::app_::impls::checker::line_checker;
+use crate::app::app_::impls::checker::{Reporters, line_checker};
 use crate::app::app_::impls::reader::input_reader;
+use crate::app::app_::impls::ticker::ticker;
 use crate::app::app_::impls::writer::output_writer;
-use crate::widgets::Statistic;
+use crate::config::{self, Chord, Cli, Config};
+use crate::widgets::{Breakdown, Results, Statistic, Toasts};
 
 impl Default for App {
   fn default() -> Self {
-    let logs: Logs = Default::default();
+    Self::new(Cli::default(), Config::default())
+  }
+}
+
+impl App {
+  /// Installs `config`, `cli` is kept to read the same files again on reload.
+  pub fn new(cli: Cli, config: Config) -> Self {
+    let logs = Logs::new(config.log.clone());
     let mut tasks = JoinSet::<()>::new();
     let event_watcher: WatchTx<UnhandledEvent> = WatchTx::default();
-    let (line_tx @ MpscTx { .. }, line_rx @ MpscRx { .. }) = mpsc::channel(1);
-    let (bucket_tx @ MpscTx { .. }, bucket_rx @ MpscRx { .. }) = mpsc::channel(1024);
+    let (line_tx @ MpscTx { .. }, line_rx @ MpscRx { .. }) = mpsc::channel(config.channels.lines);
+    let (bucket_tx @ MpscTx { .. }, bucket_rx @ MpscRx { .. }) = mpsc::channel(config.channels.results);
     let output_tx = WatchTx::new(Default::default());
     let input_tx = WatchTx::new(Default::default());
+    let (done_tx, done_rx) = mpsc::unbounded_channel();
     let state_tx = WatchTx::new(State::Iddling);
+    let settings_tx = WatchTx::new(config.run.clone());
     let statistic = Statistic::new("Processing Domains");
+    let breakdown = Breakdown::default();
+    let results = Results::default();
+    let toasts = Toasts::default();
 
     tasks.spawn(input_reader(
       line_tx,
@@ -30,37 +44,56 @@ impl Default for App {
       line_rx,
       bucket_tx,
       event_watcher.clone(),
-      logs.clone(),
-      statistic.clone(),
+      Reporters {
+        logs: logs.clone(),
+        statistic: statistic.clone(),
+        breakdown: breakdown.clone(),
+        results: results.clone(),
+        toasts: toasts.clone(),
+      },
       state_tx.subscribe(),
+      settings_tx.subscribe(),
     ));
     tasks.spawn(output_writer(
       bucket_rx,
       output_tx.subscribe(),
       event_watcher.clone(),
       logs.clone(),
+      toasts.clone(),
+      settings_tx.subscribe(),
       state_tx.subscribe(),
     ));
+    tasks.spawn(ticker(event_watcher.clone()));
+    config::install(config);
 
     Self {
       state: State::Iddling,
-      popup: None,
+      popups: PopupStack::default(),
       input: None,
       output: None,
-      change_mode: None,
       tasks,
       event_watcher,
+      done_tx,
+      done_rx,
       output_tx,
       input_tx,
       state_tx,
+      settings_tx,
       focus: true,
       scrols: ScrollStates::default(),
       logs,
+      results,
+      toasts,
+      pane: Pane::default(),
       statistic,
+      breakdown,
+      cli,
+      chord: Chord::default(),
     }
   }
 }
 
 mod checker;
 mod reader;
+mod ticker;
 mod writer;
@@ -2,30 +2,101 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use helper::UnhandledEvent;
-use tokio::time::sleep;
+use tokio::task::JoinSet;
+use tokio::time::{Instant, sleep, sleep_until, timeout};
 
-use crate::app::handler::BucketStatus;
-use crate::app::{MpscRx, MpscTx, State, WatchRx, WatchTx};
-use crate::widgets::{Log, Logs, Statistic};
+use crate::app::handler::{BucketStatus, Status};
+use crate::app::{MpscRx, MpscTx, Settings, State, WatchRx, WatchTx};
+use crate::widgets::{Breakdown, Log, Logs, Results, Statistic, Timestamp, Toasts};
+
+/// Everything a finished check reports into.
+#[derive(Debug, Clone)]
+pub struct Reporters {
+  pub logs: Logs,
+  pub statistic: Statistic,
+  pub breakdown: Breakdown,
+  pub results: Results,
+  pub toasts: Toasts,
+}
 
 pub async fn line_checker(
   mut line_rx: MpscRx<Arc<str>>,
   bucket_tx: MpscTx<BucketStatus>,
   event: WatchTx<UnhandledEvent>,
-  logs: Logs,
-  statistic: Statistic,
+  reporters: Reporters,
   state_watcher: WatchRx<State>,
+  settings: WatchRx<Settings>,
 ) {
+  let mut running = JoinSet::new();
+  let mut last_start: Option<Instant> = None;
   while let Some(line) = line_rx.recv().await {
     while !state_watcher.borrow().is_processing() {
       sleep(Duration::from_millis(16)).await
     }
-    check(line, bucket_tx.clone(), event.clone(), logs.clone(), statistic.clone()).await;
+    // Read per line so changes from the settings popup apply to the next check.
+    let settings = settings.borrow().clone();
+    while running.len() >= settings.concurrency.max(1) {
+      running.join_next().await;
+    }
+    if settings.rate_limit > 0
+      && let Some(last_start) = last_start
+    {
+      sleep_until(last_start + Duration::from_secs(1) / settings.rate_limit).await;
+    }
+    last_start = Some(Instant::now());
+    running.spawn(check(line, bucket_tx.clone(), event.clone(), reporters.clone(), settings));
   }
+  running.join_all().await;
 }
 
-pub async fn check(domain: Arc<str>, bucket_tx: MpscTx<BucketStatus>, event: WatchTx<UnhandledEvent>, logs: Logs, statistic: Statistic) {
-  match BucketStatus::new(domain).await {
+pub async fn check(domain: Arc<str>, bucket_tx: MpscTx<BucketStatus>, event: WatchTx<UnhandledEvent>, reporters: Reporters, settings: Settings) {
+  let Reporters {
+    logs,
+    statistic,
+    breakdown,
+    results,
+    toasts,
+  } = reporters;
+  let started = Instant::now();
+  let check_date = Timestamp::now();
+  let checked = timeout(
+    settings.timeout,
+    BucketStatus::check(domain.clone(), settings.provider, settings.default_region),
+  )
+  .await;
+  let result = match checked {
+    // Outside the timeout so a slow probe can't throw away the status, it has its own limit.
+    Ok(Ok(mut status)) if settings.public_probe => {
+      status.probe_public().await;
+      Ok(status)
+    }
+    Ok(result) => result,
+    Err(_) => Ok(BucketStatus::timed_out(
+      domain,
+      settings.provider,
+      settings.default_region,
+      check_date,
+      started.elapsed(),
+    )),
+  };
+
+  match result {
+    Ok(ref status) => {
+      statistic.record_timings(status.timings());
+      statistic.history().record(status.status().is_error());
+      breakdown.record(status);
+      results.add(status.clone()).await;
+      if status.status() == Status::Throttled {
+        toasts.warn("Rate limited by S3, consider slowing down");
+      }
+    }
+    Err(_) => {
+      statistic.history().record(true);
+      breakdown.record_error();
+    }
+  }
+
+  match result {
     Ok(status) => match bucket_tx.send(status.clone()).await {
       Ok(_) => {
         logs.add(Log::bucket(status)).await;
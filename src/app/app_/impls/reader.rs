@@ -58,7 +58,10 @@ pub async fn read(
   match file.read_to_string(&mut content).await {
     Ok(_) => {
       statistic.set_max(content.chars().filter(|chr| chr == &'\n').count());
-      statistic.set_current(0);
+      statistic.restart();
+      if state_watcher.borrow().is_processing() {
+        statistic.resume();
+      }
       if let Err(err) = file.rewind().await {
         logs.add(Log::error(err)).await;
         event.send_modify(|e| *e = UnhandledEvent::render());
@@ -0,0 +1,15 @@
+use std::time::Duration;
+
+use helper::UnhandledEvent;
+use tokio::time::interval;
+
+use crate::app::WatchTx;
+
+/// Redraws once a second so clocks, toasts and timed popups move without input.
+pub async fn ticker(event: WatchTx<UnhandledEvent>) {
+  let mut interval = interval(Duration::from_secs(1));
+  loop {
+    interval.tick().await;
+    event.send_modify(|e| *e = UnhandledEvent::render());
+  }
+}
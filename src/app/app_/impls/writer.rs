@@ -6,44 +6,57 @@ use tokio::sync::watch::{Receiver, Sender};
 use tokio::{fs, select};
 
 use crate::app::handler::BucketStatus;
-use crate::app::{MpscRx, State, WatchRx};
+use crate::app::{MpscRx, Settings, State, WatchRx};
 use crate::never;
-use crate::widgets::{Log, Logs};
+use crate::widgets::{Log, Logs, Toasts};
 
 pub async fn output_writer(
   mut bucket_rx: MpscRx<BucketStatus>,
   mut output_rx: WatchRx<PathBuf>,
   event: Sender<UnhandledEvent>,
   logs: Logs,
-  receiver: Receiver<State>,
+  toasts: Toasts,
+  settings: WatchRx<Settings>,
+  _receiver: Receiver<State>,
 ) {
   let mut output = output_rx.borrow_and_update().clone();
   loop {
     select! {
       new_output = output_rx.wait_for(|path| *path != output) => output = new_output.unwrap().clone(),
-      _ = writer(&mut bucket_rx, &output, &event, logs.clone()) => continue
+      _ = writer(&mut bucket_rx, &output, &event, logs.clone(), &toasts, &settings) => continue
     }
   }
 }
 
-pub async fn writer(rx: &mut MpscRx<BucketStatus>, output: &PathBuf, event: &Sender<UnhandledEvent>, logs: Logs) {
+pub async fn writer(
+  rx: &mut MpscRx<BucketStatus>,
+  output: &PathBuf,
+  event: &Sender<UnhandledEvent>,
+  logs: Logs,
+  toasts: &Toasts,
+  settings: &WatchRx<Settings>,
+) {
   let info = format!("Writer working on `{}`", output.display());
   logs.add(Log::info(info)).await;
 
   let file = match fs::File::options().create(true).append(true).open(output).await {
     Ok(file) => {
       logs.add(Log::info(format!("Opened file: {}", output.display()))).await;
+      toasts.info(format!("Output file opened: {}", output.display()));
       file
     }
     Err(err) => {
+      toasts.error(format!("Can't open output {}: {err}", output.display()));
       logs.add(Log::error(err)).await;
       never!()
     }
   };
 
   let mut writer = BufWriter::new(file);
-  while let Some(bucket) = rx.recv().await.map(String::from) {
-    if let Err(err) = writer.write_all(bucket.as_bytes()).await {
+  while let Some(bucket) = rx.recv().await {
+    // Read per line, a format change applies to the next result.
+    let format = settings.borrow().output_format.resolve(output);
+    if let Err(err) = writer.write_all(format.format(&bucket).as_bytes()).await {
       logs.add(Log::error(err)).await;
       event.send_modify(|e| *e = UnhandledEvent::render());
     } else {
@@ -0,0 +1,106 @@
+use std::io;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+use tokio::fs;
+
+/// Picked from the file extension, plain text otherwise.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum ExportFormat {
+  #[default]
+  Text,
+  /// One object per line.
+  Json,
+  Markdown,
+}
+
+impl ExportFormat {
+  pub fn from_path(path: &Path) -> Self {
+    match path.extension().and_then(|ext| ext.to_str()) {
+      Some("json" | "jsonl" | "ndjson") => Self::Json,
+      Some("md" | "markdown") => Self::Markdown,
+      _ => Self::Text,
+    }
+  }
+}
+
+/// Snapshot of what a pane shows, column names first.
+#[derive(Debug, Default, Clone)]
+pub struct Export {
+  pub columns: Vec<String>,
+  pub rows: Vec<Vec<String>>,
+}
+
+impl Export {
+  pub fn lines(&self, format: ExportFormat) -> Vec<String> {
+    match format {
+      ExportFormat::Text => self.rows.iter().map(|row| row.join("\t")).collect(),
+      ExportFormat::Json => self
+        .rows
+        .iter()
+        .map(|row| {
+          let object = self
+            .columns
+            .iter()
+            .zip(row)
+            .map(|(column, value)| (column.to_lowercase(), Value::from(value.as_str())))
+            .collect::<Map<_, _>>();
+          Value::Object(object).to_string()
+        })
+        .collect(),
+      ExportFormat::Markdown => {
+        let row = |cells: &[String]| {
+          let cells = cells.iter().map(|cell| cell.replace('|', "\\|")).collect::<Vec<_>>();
+          format!("| {} |", cells.join(" | "))
+        };
+        let separator = format!("|{}", "---|".repeat(self.columns.len()));
+        [row(&self.columns), separator]
+          .into_iter()
+          .chain(self.rows.iter().map(|cells| row(cells)))
+          .collect()
+      }
+    }
+  }
+
+  /// Overwrites `path`, returns how many rows were written, headers aside.
+  pub async fn write(&self, path: &Path) -> io::Result<usize> {
+    let mut content = self.lines(ExportFormat::from_path(path)).join("\n");
+    content.push('\n');
+    fs::write(path, content).await?;
+    Ok(self.rows.len())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_lines() {
+    let export = Export {
+      columns: vec![String::from("Level"), String::from("Message")],
+      rows: vec![vec![String::from("INFO"), String::from("a|b")]],
+    };
+
+    assert_eq!(export.lines(ExportFormat::Text), ["INFO\ta|b"]);
+    assert_eq!(export.lines(ExportFormat::Json), [r#"{"level":"INFO","message":"a|b"}"#]);
+    assert_eq!(
+      export.lines(ExportFormat::Markdown),
+      ["| Level | Message |", "|---|---|", "| INFO | a\\|b |"]
+    );
+    assert_eq!(ExportFormat::from_path(Path::new("out.md")), ExportFormat::Markdown);
+  }
+
+  #[tokio::test]
+  async fn test_write() {
+    let export = Export {
+      columns: vec![String::from("Name")],
+      rows: vec![vec![String::from("a")], vec![String::from("b")]],
+    };
+    let path = std::env::temp_dir().join(format!("s3-creator-export-{}.md", std::process::id()));
+
+    assert_eq!(export.write(&path).await.unwrap(), 2);
+    assert_eq!(fs::read_to_string(&path).await.unwrap().lines().count(), 4);
+    fs::remove_file(path).await.unwrap();
+  }
+}
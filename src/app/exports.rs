@@ -1,4 +1,7 @@
 pub use super::app_::*;
+pub use super::export::*;
 pub use super::popup::*;
 pub use super::scroll_states::*;
+pub use super::settings::*;
 pub use super::state::*;
+pub use super::throughput::*;
@@ -1,21 +1,52 @@
 use std::fs;
+use std::io::BufReader;
 use std::net::Ipv4Addr;
-use std::sync::{Arc, LazyLock};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
+use anyhow::Context;
 use cidr::Ipv4Cidr;
-use serde::Deserialize;
-use strum::EnumIs;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIs};
 
-use crate::app::handler::get_ip;
+use crate::app::handler::{Resolution, get_ip, resolve};
+use crate::config;
 
-pub static AWS_IP: LazyLock<AwsRanges> = LazyLock::new(|| serde_json::from_reader(fs::File::open("ip-ranges.json").unwrap()).unwrap());
+static AWS_IP: Mutex<Option<(PathBuf, Arc<AwsRanges>)>> = Mutex::new(None);
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Default)]
 pub struct AwsRanges {
   pub prefixes: Arc<[Prefix]>,
 }
 
 impl AwsRanges {
+  pub fn load(path: &Path) -> anyhow::Result<Self> {
+    let file = fs::File::open(path).with_context(|| format!("Can't open {}", path.display()))?;
+    serde_json::from_reader(BufReader::new(file)).with_context(|| format!("{} is not an AWS ip-ranges.json", path.display()))
+  }
+
+  /// Parses `path` for `current`, so the check of a configuration leaves its ranges ready.
+  pub fn preload(path: &Path) -> anyhow::Result<()> {
+    let ranges = Arc::new(Self::load(path)?);
+    *AWS_IP.lock().unwrap() = Some((path.to_path_buf(), ranges));
+    Ok(())
+  }
+
+  /// Ranges of the configured file, read again when a reload points to another one.
+  pub fn current() -> Arc<Self> {
+    let path = config::current().network.ip_ranges.clone();
+    let mut cached = AWS_IP.lock().unwrap();
+    match cached.as_ref() {
+      Some((cached_path, ranges)) if *cached_path == path => ranges.clone(),
+      _ => {
+        // Validated on load, a file gone since leaves every address outside AWS.
+        let ranges = Arc::new(Self::load(&path).unwrap_or_default());
+        *cached = Some((path, ranges.clone()));
+        ranges
+      }
+    }
+  }
+
   pub fn get_prefix(&self, ip: &Ipv4Addr, service: impl Into<Option<IpService>>) -> Option<&Prefix> {
     let service = service.into();
     let s = service.as_ref();
@@ -163,30 +194,57 @@ impl Region {
     #[cfg(test)]
     eprintln!("Parse IP success! {ip}");
 
-    let region = AWS_IP.get_prefix(&ip, None).map(|prefix| prefix.region)?;
+    let region = AwsRanges::current().get_prefix(&ip, None).map(|prefix| prefix.region)?;
     #[cfg(test)]
     eprintln!("Parse Region success! {region}");
     Some(region)
   }
 }
+impl Region {
+  /// Exact region name, unlike `From<&str>` this doesn't fall back to `us-east-1`.
+  pub fn from_name(value: &str) -> Option<Self> {
+    let value = value.trim();
+    <Self as strum::VariantArray>::VARIANTS
+      .iter()
+      .find(|region| region.as_ref().eq_ignore_ascii_case(value))
+      .copied()
+  }
+}
 impl From<&str> for Region {
   fn from(value: &str) -> Self {
-    let value = if value.is_empty() {
-      return Self::UsEast1;
-    } else {
-      value.to_lowercase()
-    };
+    Self::from_name(value).unwrap_or(Self::UsEast1)
+  }
+}
 
-    let variant_str = <Region as strum::VariantNames>::VARIANTS;
-    let variant_name = <Region as strum::VariantArray>::VARIANTS;
-    let variants = variant_str.iter().zip(variant_name);
-    for (variant, region) in variants {
-      if **variant == *value {
-        return *region;
-      }
-    }
+/// How the region of a result was determined, from most to least trustworthy.
+#[derive(Default, Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Display, EnumIs, Serialize)]
+pub enum RegionSource {
+  /// Signed `GetBucketLocation`.
+  #[strum(to_string = "bucket location")]
+  Location,
+  /// `x-amz-bucket-region` response header.
+  #[strum(to_string = "region header")]
+  Header,
+  /// `<Region>` or `<Endpoint>` of an S3 error document.
+  #[strum(to_string = "error body")]
+  ErrorBody,
+  /// The resolved address belongs to an AWS range.
+  #[strum(to_string = "AWS IP range")]
+  IpRange,
+  #[default]
+  #[strum(to_string = "default region")]
+  Default,
+}
 
-    Self::UsEast1
+impl RegionSource {
+  /// Starting point before S3 has told us anything, along with the resolution it came from.
+  pub async fn guess(name: &str, fallback: Region) -> (Region, Self, Resolution) {
+    let resolution = resolve(name).await;
+    let ranges = AwsRanges::current();
+    match resolution.ip.and_then(|ip| ranges.get_prefix(&ip, None)) {
+      Some(prefix) => (prefix.region, Self::IpRange, resolution),
+      None => (fallback, Self::Default, resolution),
+    }
   }
 }
 
@@ -217,6 +275,14 @@ mod tests {
     assert!(prefix.region.is_us_east_2());
   }
 
+  #[test]
+  fn test_from_name() {
+    assert_eq!(Region::from_name("eu-west-2"), Some(Region::EuWest2));
+    assert_eq!(Region::from_name(" US-EAST-1 "), Some(Region::UsEast1));
+    assert_eq!(Region::from_name("mars-north-1"), None);
+    assert_eq!(Region::from("mars-north-1"), Region::UsEast1);
+  }
+
   #[tokio::test]
   async fn test_from_ip() {
     let region = Region::from_ip("s3.ucod.kr").await.unwrap();
@@ -1,89 +1,331 @@
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use Status::*;
 use ratatui::prelude::Stylize;
 use ratatui::style::Color;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::ListItem;
-use strum::{Display, EnumIs};
+use serde::{Deserialize, Serialize, Serializer};
+use strum::{Display, EnumIs, VariantArray};
 
 use super::*;
-use crate::widgets::Timestamp;
+use crate::widgets::{Details, Timestamp};
 
-#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Serialize)]
 pub struct BucketStatus {
   name: Arc<str>,
   region: Region,
+  region_source: RegionSource,
   status: Status,
-  code: StatusCode,
+  /// `None` when the request failed before S3 answered.
+  #[serde(serialize_with = "serialize_code")]
+  code: Option<StatusCode>,
+  /// S3 error code from the response body, e.g. `NoSuchBucket` or `PermanentRedirect`.
+  error_code: Option<Arc<str>>,
   check_date: Timestamp,
+  probe: Probe,
+  access: Access,
+  public: Option<PublicAccess>,
+  timings: Timings,
+  dns: Resolution,
+  /// `HEADERS_OF_INTEREST` of the last response.
+  headers: BTreeMap<&'static str, Arc<str>>,
+}
+
+fn serialize_code<S: Serializer>(code: &Option<StatusCode>, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+  match code {
+    Some(code) => serializer.serialize_some(&code.as_u16()),
+    None => serializer.serialize_none(),
+  }
 }
 
 impl BucketStatus {
   const BASE: &'static str = "https://s3.region.amazonaws.com/bucket";
+  const MAX_REGION_REDIRECTS: usize = 3;
+  const PUBLIC_PROBE_LIMIT: Duration = Duration::from_secs(15);
 
+  /// Signed probe when credentials are configured, anonymous otherwise or when signing is rejected.
   pub async fn new(name: impl Into<Arc<str>>) -> Result<Self> {
+    Self::check(name, Provider::Auto, Region::UsEast1).await
+  }
+
+  /// `fallback` is where the first request goes when the address gives no hint of the region.
+  pub async fn check(name: impl Into<Arc<str>>, provider: Provider, fallback: Region) -> Result<Self> {
     let name = name.into();
+    if let Some(credentials) = CREDENTIALS.as_ref().filter(|_| !provider.is_anonymous()) {
+      match Self::authenticated(Self::BASE, name.clone(), credentials, fallback).await {
+        Ok(status) if !status.status.is_error() || provider.is_credentials() => return Ok(status),
+        Err(error) if provider.is_credentials() => return Err(error),
+        _ => {}
+      }
+    }
+
+    Self::anonymous(name, fallback).await
+  }
+
+  /// A check cut short by the run timeout after `elapsed`, nothing but the time it took is known.
+  pub fn timed_out(name: impl Into<Arc<str>>, provider: Provider, fallback: Region, check_date: Timestamp, elapsed: Duration) -> Self {
+    let signed = CREDENTIALS.is_some() && !provider.is_anonymous();
+    let probe = if signed { Probe::Authenticated } else { Probe::Anonymous };
+    let mut this = Self::with(name.into(), fallback, check_date, probe);
+    this.status = Status::Timeout;
+    this.timings.total = elapsed;
+    this
+  }
+
+  /// Unchecked status for tests of the views.
+  #[cfg(test)]
+  pub fn unchecked(name: &str) -> Self {
+    Self::with(Arc::from(name), Region::UsEast1, Timestamp::now(), Probe::Anonymous)
+  }
+
+  fn with(name: Arc<str>, region: Region, check_date: Timestamp, probe: Probe) -> Self {
+    Self {
+      name,
+      region,
+      region_source: RegionSource::default(),
+      status: Status::default(),
+      code: None,
+      error_code: None,
+      check_date,
+      probe,
+      access: Access::Unknown,
+      public: None,
+      timings: Timings::default(),
+      dns: Resolution::default(),
+      headers: BTreeMap::new(),
+    }
+  }
+
+  fn failed(mut self, error: &Error) -> Self {
+    self.status = Status::from_error(error);
+    self
+  }
+
+  /// `base` with its `region` and `bucket` placeholders filled in.
+  fn url(base: &str, region: Region, name: &str) -> String {
+    base.replace("region", region.as_ref()).replace("bucket", name)
+  }
+
+  /// Requests to `base`, see `BASE`. A request that fails before S3 answers gives an error status, like `anonymous`.
+  pub async fn authenticated(base: &str, name: Arc<str>, credentials: &Credentials, fallback: Region) -> Result<Self> {
+    let started = Instant::now();
     let check_date = Timestamp::now();
-    let mut region = Region::from_ip(name.as_ref()).await.unwrap_or(Region::UsEast1);
-    let url = Self::BASE.replace("region", region.as_ref()).replace("bucket", name.as_ref());
-
-    let response = head(&url).await?;
-    if response.status().as_u16() == 404 {
-      return Ok(BucketStatus {
-        name: name.clone(),
-        region,
-        status: Available,
-        code: StatusCode::NOT_FOUND,
-        check_date,
-      });
+    let (dns, (mut region, mut region_source, resolution)) = timed(RegionSource::guess(&name, fallback)).await;
+    let mut timings = Timings { dns, ..Default::default() };
+    let timer = ConnectTimer::default();
+    let bucket = |region| Url::parse(&Self::url(base, region, &name)).expect("bucket url is always valid");
+    let unanswered = |error: Error, region, region_source, mut timings: Timings| {
+      timings.total = started.elapsed();
+      let mut this = Self::with(name.clone(), region, check_date, Probe::Authenticated);
+      this.region_source = region_source;
+      this.timings = timings;
+      this.dns = resolution.clone();
+      this.failed(&error)
+    };
+
+    let (elapsed, response) = timed(head_bucket(bucket(region), region, credentials, &timer)).await;
+    timings.add_request(elapsed, &timer);
+    let mut response = match response {
+      Ok(response) => response,
+      Err(error) => return Ok(unanswered(error, region, region_source, timings)),
+    };
+    for _ in 0..Self::MAX_REGION_REDIRECTS {
+      let Some(actual) = header_region(&response) else { break };
+      region_source = RegionSource::Header;
+      if actual == region {
+        break;
+      }
+      region = actual;
+      let (elapsed, redirected) = timed(head_bucket(bucket(region), region, credentials, &timer)).await;
+      timings.add_request(elapsed, &timer);
+      response = match redirected {
+        Ok(response) => response,
+        Err(error) => return Ok(unanswered(error, region, region_source, timings)),
+      };
+    }
+
+    let code = response.status();
+    let headers = headers_of_interest(&response);
+    let access = match code.as_u16() {
+      200 => Access::Accessible,
+      403 => Access::Denied,
+      _ => Access::Unknown,
+    };
+    if access.is_accessible() {
+      let (elapsed, location) = timed(get_bucket_location(bucket(region), region, credentials, &timer)).await;
+      timings.add_request(elapsed, &timer);
+      if let Ok(Some(location)) = location {
+        region = location;
+        region_source = RegionSource::Location;
+      }
     }
+    timings.total = started.elapsed();
+
+    let mut this = Self::with(name, region, check_date, Probe::Authenticated);
+    this.status = code.into();
+    this.code = Some(code);
+    this.access = access;
+    this.region_source = region_source;
+    this.timings = timings;
+    this.dns = resolution;
+    this.headers = headers;
+    Ok(this)
+  }
+
+  /// Header-first: whatever region S3 names wins over the IP range guess, and a non-conclusive
+  /// answer from the wrong region is retried up to `MAX_REGION_REDIRECTS` times.
+  pub async fn anonymous(name: Arc<str>, fallback: Region) -> Result<Self> {
+    let started = Instant::now();
+    let check_date = Timestamp::now();
+    let (dns, (mut region, mut region_source, resolution)) = timed(RegionSource::guess(&name, fallback)).await;
+    let mut timings = Timings { dns, ..Default::default() };
+    let timer = ConnectTimer::default();
+    let mut redirects = 0;
 
-    if response.status().is_redirection() {
-      match response
-        .headers()
-        .get("x-amz-bucket-region")
-        .and_then(|h| Some(Region::from(h.to_str().ok()?)))
-      {
-        Some(r) => {
-          region = r;
+    loop {
+      let url = Self::url(Self::BASE, region, &name);
+      let (elapsed, response) = timed(head(&url, &timer)).await;
+      timings.add_request(elapsed, &timer);
+      let response = match response {
+        Ok(response) => response,
+        Err(error) => {
+          timings.total = started.elapsed();
+          let mut this = Self::with(name, region, check_date, Probe::Anonymous);
+          this.region_source = region_source;
+          this.timings = timings;
+          this.dns = resolution;
+          return Ok(this.failed(&error));
         }
-        None => {
-          return Ok(Self {
-            name,
-            region,
-            status: Unknown,
-            code: response.status(),
-            check_date,
-          });
+      };
+
+      let code = response.status();
+      let headers = headers_of_interest(&response);
+      let conclusive = matches!(code.as_u16(), 200..=299 | 403 | 404);
+      let mut actual = header_region(&response).map(|actual| (actual, RegionSource::Header));
+
+      // The body costs a second request, only read it when the headers leave the status or region open.
+      let undecided = match Status::classify(code, None) {
+        Status::Unknown(_) => true,
+        Status::WrongRegion => actual.is_none(),
+        _ => false,
+      };
+      let mut error = None;
+      if undecided {
+        let (elapsed, parsed) = timed(s3_error(&url, &timer)).await;
+        timings.add_request(elapsed, &timer);
+        error = parsed;
+        actual = actual.or_else(|| Some((error.as_ref()?.region?, RegionSource::ErrorBody)));
+      }
+
+      if let Some((actual, source)) = actual {
+        region_source = source;
+        if actual != region {
+          region = actual;
+          if !conclusive && redirects < Self::MAX_REGION_REDIRECTS {
+            redirects += 1;
+            continue;
+          }
         }
       }
-    }
 
-    let url = Self::BASE.replace("region", region.as_ref()).replace("bucket", &name);
-    let response = head(&url).await?;
-    let status: Status = response.status().into();
-    let region = if !status.is_available() { None } else { Region::from_ip(&name).await }.unwrap_or(region);
+      timings.total = started.elapsed();
+      let error_code = error.and_then(|error| error.code);
+      let status = match Status::classify(code, error_code.as_deref()) {
+        // Anonymous HeadBucket only succeeds when everyone may list the bucket.
+        Status::Exists if code.is_success() => Status::ExistsPublic,
+        status => status,
+      };
 
-    Ok(Self {
-      name,
-      region,
-      status,
-      code: response.status(),
-      check_date,
-    })
+      let mut this = Self::with(name, region, check_date, Probe::Anonymous);
+      this.status = status;
+      this.code = Some(code);
+      this.error_code = error_code;
+      this.region_source = region_source;
+      this.timings = timings;
+      this.dns = resolution;
+      this.headers = headers;
+      return Ok(this);
+    }
+  }
+  pub fn name(&self) -> &str {
+    &self.name
   }
   pub fn timestamp(&self) -> Timestamp {
     self.check_date
   }
+  pub fn probe(&self) -> Probe {
+    self.probe
+  }
+  pub fn access(&self) -> Access {
+    self.access
+  }
+  pub fn public(&self) -> Option<PublicAccess> {
+    self.public
+  }
+  pub fn region(&self) -> Region {
+    self.region
+  }
+  pub fn region_source(&self) -> RegionSource {
+    self.region_source
+  }
+  pub fn status(&self) -> Status {
+    self.status
+  }
+  pub fn code(&self) -> Option<StatusCode> {
+    self.code
+  }
+  pub fn error_code(&self) -> Option<&str> {
+    self.error_code.as_deref()
+  }
+  pub fn timings(&self) -> Timings {
+    self.timings
+  }
+  pub fn dns(&self) -> &Resolution {
+    &self.dns
+  }
+  pub fn headers(&self) -> &BTreeMap<&'static str, Arc<str>> {
+    &self.headers
+  }
+  pub fn is_taken(&self) -> bool {
+    self.status.is_taken()
+  }
+
+  /// Second phase for taken buckets, anything else is left untouched.
+  /// Past `PUBLIC_PROBE_LIMIT` the status stands without its findings.
+  pub async fn probe_public(&mut self) {
+    if !self.is_taken() {
+      return;
+    }
+
+    let (elapsed, public) = timed(tokio::time::timeout(
+      Self::PUBLIC_PROBE_LIMIT,
+      PublicAccess::probe(&self.name, self.region),
+    ))
+    .await;
+    self.timings.request += elapsed;
+    self.timings.total += elapsed;
+    let Ok(public) = public else {
+      return;
+    };
+    if public.is_public() {
+      self.status = Status::ExistsPublic;
+    }
+    self.public = Some(public);
+  }
 }
 
 impl Display for BucketStatus {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     use ratatui::symbols::line::DOUBLE_VERTICAL_LEFT as SEP;
 
-    write!(f, "{SEP}{}{SEP}{}({})", self.status, self.name, self.region)
+    write!(f, "{SEP}{}{SEP}{}({})", self.status, self.name, self.region)?;
+    match self.public {
+      Some(public) if public.is_public() => write!(f, "[public:{}]", public.names().join(",")),
+      _ => Ok(()),
+    }
   }
 }
 
@@ -93,26 +335,42 @@ impl From<BucketStatus> for String {
   }
 }
 
-impl<'s> From<&'s BucketStatus> for Line<'s> {
-  fn from(value: &'s BucketStatus) -> Self {
+impl BucketStatus {
+  /// The activity line without its timestamp.
+  pub fn content_line(&self) -> Line<'_> {
     let mut spans: Vec<Span> = vec![
       Span::raw(ratatui::symbols::line::DOUBLE_VERTICAL_LEFT).fg(Color::DarkGray),
-      value.status.into(),
+      self.status.into(),
+    ];
+    if let Some(public) = self.public {
+      spans.extend(public.badges());
+    }
+    spans.extend([
       Span::raw(ratatui::symbols::line::DOUBLE_VERTICAL_RIGHT).fg(Color::DarkGray),
-      Span::raw(value.name.as_ref()),
+      Span::raw(self.name.as_ref()).fg(self.status.color()),
       Span::raw("("),
-      Span::raw(value.region.to_string()),
+      Span::raw(self.region.to_string()),
       Span::raw(")"),
-    ];
-
-    for (pos, span) in value.check_date.as_spans().into_iter().enumerate() {
-      spans.insert(pos, span);
+    ]);
+    if let Some(ref error_code) = self.error_code {
+      spans.push(Span::raw(format!(" {error_code}")).fg(Color::DarkGray));
+    }
+    if !self.access.is_unknown() {
+      spans.push(Span::raw(format!(" [{}]", self.access)).fg(Color::DarkGray));
     }
 
     Line::from_iter(spans)
   }
 }
 
+impl<'s> From<&'s BucketStatus> for Line<'s> {
+  fn from(value: &'s BucketStatus) -> Self {
+    let mut line = Line::from_iter(value.check_date.as_spans());
+    line.spans.extend(value.content_line().spans);
+    line
+  }
+}
+
 impl<'s> From<&'s BucketStatus> for ListItem<'s> {
   fn from(value: &'s BucketStatus) -> Self {
     let line: Line<'s> = value.into();
@@ -120,56 +378,113 @@ impl<'s> From<&'s BucketStatus> for ListItem<'s> {
   }
 }
 
-#[derive(Default, Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Display, EnumIs)]
-enum Status {
-  #[default]
-  Unknown,
-  Available,
-  Unavailable,
-}
-impl From<StatusCode> for Status {
-  fn from(status: StatusCode) -> Self {
-    if status.is_success() {
-      Unavailable
-    } else if status.is_redirection() {
-      Unknown
-    } else {
-      match status.as_u16() {
-        404 => Available,
-        403 => Unavailable,
-        _ => Unknown,
+impl From<&BucketStatus> for Details {
+  fn from(value: &BucketStatus) -> Self {
+    let millis = |duration: std::time::Duration| format!("{}ms", duration.as_millis());
+    let public = match value.public {
+      Some(public) if public.is_public() => public.names().join(", "),
+      Some(_) => String::from("not public"),
+      None => String::from("not probed"),
+    };
+
+    let dns = match (value.dns.cnames.as_slice(), value.dns.ip) {
+      ([], None) => String::from("unresolved"),
+      (cnames, ip) => {
+        let ip = ip.map(|ip| ip.to_string()).unwrap_or_else(|| String::from("no A record"));
+        std::iter::once(value.name.as_ref())
+          .chain(cnames.iter().map(AsRef::as_ref))
+          .chain([ip.as_str()])
+          .collect::<Vec<_>>()
+          .join(" → ")
       }
-    }
+    };
+
+    let details = Details::new(format!("🪣 {}", value.name))
+      .field("Status", format!("{} {}", value.status.icon(), value.status))
+      .field("Region", format!("{} (from {})", value.region, value.region_source))
+      .field(
+        "HTTP status",
+        value.code.map(|code| code.to_string()).unwrap_or_else(|| String::from("-")),
+      )
+      .field("S3 error", value.error_code.as_deref().unwrap_or("-"))
+      .field("Probe", value.probe.to_string())
+      .field("Signed access", value.access.to_string())
+      .field("Public", public)
+      .field("DNS", millis(value.timings.dns))
+      .field("Connect", value.timings.connect.map(millis).unwrap_or_else(|| String::from("-")))
+      .field("Requests", millis(value.timings.request))
+      .field("Total", millis(value.timings.total))
+      .field("Checked at", value.check_date.to_rfc3339())
+      .field("DNS chain", dns);
+
+    value
+      .headers
+      .iter()
+      .fold(details, |details, (name, header)| details.field(name, header.to_string()))
   }
 }
-impl From<Status> for Span<'static> {
-  fn from(value: Status) -> Self {
-    match value {
-      Unknown => Span::raw("❔"),
-      Available => Span::raw("✅"),
-      Unavailable => Span::raw("⛔"),
-    }
-  }
+
+/// Which probes a check may use.
+#[derive(Default, Debug, Eq, PartialEq, Copy, Clone, Display, EnumIs, VariantArray, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+  /// Signed when credentials are configured, anonymous when there are none or signing fails.
+  #[default]
+  Auto,
+  /// Signed results are kept even when S3 rejects them, anonymous only without credentials.
+  Credentials,
+  Anonymous,
+}
+
+/// Which kind of request produced the result.
+#[derive(Default, Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Display, EnumIs, Serialize)]
+pub enum Probe {
+  #[default]
+  Anonymous,
+  Authenticated,
+}
+
+/// Only known for signed probes: S3 answers 200 to the owner and to anyone a bucket policy or ACL lets in,
+/// 403 to everyone else, so this says nothing about who owns the bucket.
+#[derive(Default, Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Display, EnumIs, Serialize)]
+pub enum Access {
+  #[default]
+  Unknown,
+  Accessible,
+  Denied,
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  #[tokio::test]
+  async fn test_unreachable_signed() {
+    // Not a domain, so guessing the region doesn't wait on the network either.
+    let name = Arc::from("unreachable bucket");
+    let credentials = Credentials::new("AKIDEXAMPLE", "secret", None);
+    let unreachable = BucketStatus::authenticated("https://s3.region.invalid/bucket", name, &credentials, Region::EuWest1)
+      .await
+      .unwrap();
+    assert_eq!(unreachable.status, Status::DnsFailure);
+    assert_eq!((unreachable.probe, unreachable.region), (Probe::Authenticated, Region::EuWest1));
+    assert!(unreachable.code.is_none() && unreachable.timings.total >= unreachable.timings.request);
+  }
+
   #[tokio::test]
   async fn test_available_bucket() {
     let domain = "zvonar.dev";
     let bucket_status = BucketStatus::new(domain).await.unwrap();
-    assert!(bucket_status.status.is_unavailable());
-    assert_eq!(bucket_status.code.as_u16(), 403);
+    assert!(bucket_status.status.is_exists());
+    assert_eq!(bucket_status.code.map(|code| code.as_u16()), Some(403));
 
     let domain = "s3.ucod.kr";
     let bucket_status = BucketStatus::new(domain).await.unwrap();
-    assert!(bucket_status.status.is_unavailable(), "Bucket should be unavailable {bucket_status:#?}");
+    assert!(bucket_status.is_taken(), "Bucket should be taken {bucket_status:#?}");
     assert_ne!(
-      bucket_status.code.as_u16(),
-      404,
-      "Bucket status code should not 404 {}",
+      bucket_status.code.map(|code| code.as_u16()),
+      Some(404),
+      "Bucket status code should not 404 {:?}",
       bucket_status.code
     );
   }
@@ -0,0 +1,123 @@
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock};
+use std::{env, fs};
+
+pub static CREDENTIALS: LazyLock<Option<Credentials>> = LazyLock::new(Credentials::load);
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Credentials {
+  access_key_id: Arc<str>,
+  secret_access_key: Arc<str>,
+  session_token: Option<Arc<str>>,
+}
+
+impl Credentials {
+  const DEFAULT_PROFILE: &'static str = "default";
+
+  pub fn new(access_key_id: impl Into<Arc<str>>, secret_access_key: impl Into<Arc<str>>, session_token: Option<Arc<str>>) -> Self {
+    Self {
+      access_key_id: access_key_id.into(),
+      secret_access_key: secret_access_key.into(),
+      session_token,
+    }
+  }
+
+  /// Environment variables win over the shared credentials file, same order as the AWS CLI.
+  pub fn load() -> Option<Self> {
+    Self::from_env().or_else(|| {
+      let profile = env::var("AWS_PROFILE").unwrap_or_else(|_| Self::DEFAULT_PROFILE.to_string());
+      Self::from_profile(&profile)
+    })
+  }
+
+  pub fn from_env() -> Option<Self> {
+    let access_key_id = env::var("AWS_ACCESS_KEY_ID").ok().filter(|v| !v.is_empty())?;
+    let secret_access_key = env::var("AWS_SECRET_ACCESS_KEY").ok().filter(|v| !v.is_empty())?;
+    let session_token = env::var("AWS_SESSION_TOKEN").ok().filter(|v| !v.is_empty()).map(Arc::from);
+
+    Some(Self::new(access_key_id, secret_access_key, session_token))
+  }
+
+  pub fn from_profile(profile: &str) -> Option<Self> {
+    let content = fs::read_to_string(Self::shared_file()?).ok()?;
+    Self::parse_profile(&content, profile)
+  }
+
+  pub fn parse_profile(content: &str, profile: &str) -> Option<Self> {
+    let mut in_profile = false;
+    let mut access_key_id = None;
+    let mut secret_access_key = None;
+    let mut session_token = None;
+
+    for line in content.lines().map(str::trim) {
+      if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+        continue;
+      }
+      if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+        in_profile = section.trim() == profile;
+        continue;
+      }
+      if !in_profile {
+        continue;
+      }
+      let Some((key, value)) = line.split_once('=') else { continue };
+      let value = value.trim();
+      match key.trim() {
+        "aws_access_key_id" => access_key_id = Some(value.to_string()),
+        "aws_secret_access_key" => secret_access_key = Some(value.to_string()),
+        "aws_session_token" => session_token = Some(Arc::from(value)),
+        _ => {}
+      }
+    }
+
+    Some(Self::new(access_key_id?, secret_access_key?, session_token))
+  }
+
+  fn shared_file() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("AWS_SHARED_CREDENTIALS_FILE") {
+      return Some(PathBuf::from(path));
+    }
+    env::home_dir().map(|home| home.join(".aws").join("credentials"))
+  }
+
+  pub fn access_key_id(&self) -> &str {
+    &self.access_key_id
+  }
+  pub fn secret_access_key(&self) -> &str {
+    &self.secret_access_key
+  }
+  pub fn session_token(&self) -> Option<&str> {
+    self.session_token.as_deref()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const SHARED: &str = "
+[default]
+aws_access_key_id = AKIDDEFAULT
+aws_secret_access_key = secret-default
+
+# work account
+[work]
+aws_access_key_id=AKIDWORK
+aws_secret_access_key=secret-work
+aws_session_token=token-work
+";
+
+  #[test]
+  fn test_parse_profile() {
+    let default = Credentials::parse_profile(SHARED, "default").unwrap();
+    assert_eq!(default.access_key_id(), "AKIDDEFAULT");
+    assert_eq!(default.secret_access_key(), "secret-default");
+    assert!(default.session_token().is_none());
+
+    let work = Credentials::parse_profile(SHARED, "work").unwrap();
+    assert_eq!(work.access_key_id(), "AKIDWORK");
+    assert_eq!(work.session_token(), Some("token-work"));
+
+    assert!(Credentials::parse_profile(SHARED, "missing").is_none());
+  }
+}
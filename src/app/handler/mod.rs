@@ -1,67 +1,265 @@
+use std::collections::BTreeMap;
 use std::net::Ipv4Addr;
-use std::sync::LazyLock;
+use std::sync::{Arc, Mutex};
 
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use clap::ValueEnum;
 use reqwest::*;
+use serde::{Deserialize, Serialize};
+use strum::Display;
 use trust_dns_resolver::TokioAsyncResolver;
 use trust_dns_resolver::config::{ResolverConfig, ResolverOpts};
+use trust_dns_resolver::error::ResolveError;
+
+use crate::config;
 
 mod bucket_status;
 pub use bucket_status::*;
 
+mod status;
+pub use status::*;
+
 mod aws_ranges;
 pub use aws_ranges::*;
 
-pub static TAR: LazyLock<TokioAsyncResolver> = LazyLock::new(|| TokioAsyncResolver::tokio(ResolverConfig::cloudflare(), ResolverOpts::default()));
+mod credentials;
+pub use credentials::*;
 
-macro_rules! ttlen {
-  ($tt:tt) => {
-    1usize
-  };
+mod sigv4;
+pub use sigv4::*;
+
+mod public_access;
+pub use public_access::*;
+
+mod timings;
+pub use timings::*;
+
+/// Headers of the anonymous console requests, `[network.headers]` replaces them.
+pub const BUCKET_HEAD: [(&str, &str); 13] = [
+  (
+    "user-agent",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:139.0) Gecko/20100101 Firefox/139.0",
+  ),
+  ("accept", "*/*"),
+  ("accept-language", "en-US,en;q=0.5"),
+  ("x-amz-user-agent", "aws-sdk-js/2.1687.0"),
+  ("x-amz-s3-console-op-name", "HeadBucket"),
+  ("s3v3", "s3v3"),
+  ("origin", "https://us-east-1.console.aws.amazon.com"),
+  ("sec-gpc", "1"),
+  ("connection", "keep-alive"),
+  ("referer", "https://us-east-1.console.aws.amazon.com/"),
+  ("sec-fetch-dest", "empty"),
+  ("sec-fetch-mode", "cors"),
+  ("sec-fetch-site", "cross-site"),
+];
+
+/// Upstream DNS servers names are resolved with.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Display, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Nameservers {
+  #[default]
+  Cloudflare,
+  Google,
+  Quad9,
+  /// The system configuration, `/etc/resolv.conf` on unix.
+  System,
 }
 
-macro_rules! header_static {
-  {
-    $name:ident = [
-      $($key:literal: $value:literal $(,)?)*
-    ]
-  } => {
-    const $name: [(HeaderName, HeaderValue); $(ttlen!($key)+)+0usize] = [
-      $((::reqwest::header::HeaderName::from_static($key), ::reqwest::header::HeaderValue::from_static($key))),+
-    ];
-  };
+impl Nameservers {
+  pub fn resolver(self) -> std::result::Result<TokioAsyncResolver, ResolveError> {
+    let config = match self {
+      Self::Cloudflare => ResolverConfig::cloudflare(),
+      Self::Google => ResolverConfig::google(),
+      Self::Quad9 => ResolverConfig::quad9(),
+      Self::System => return TokioAsyncResolver::tokio_from_system_conf(),
+    };
+    Ok(TokioAsyncResolver::tokio(config, ResolverOpts::default()))
+  }
+}
+
+static TAR: Mutex<Option<(Nameservers, TokioAsyncResolver)>> = Mutex::new(None);
+
+/// Resolver of the current configuration, built again when a reload changes the nameservers.
+fn resolver() -> TokioAsyncResolver {
+  let nameservers = config::current().network.resolver;
+  let mut tar = TAR.lock().unwrap();
+  match tar.as_ref() {
+    Some((cached, resolver)) if *cached == nameservers => resolver.clone(),
+    _ => {
+      // Validated on load, the fallback only covers a system configuration removed since.
+      let resolver = nameservers
+        .resolver()
+        .unwrap_or_else(|_| TokioAsyncResolver::tokio(ResolverConfig::cloudflare(), ResolverOpts::default()));
+      *tar = Some((nameservers, resolver.clone()));
+      resolver
+    }
+  }
 }
 
-header_static! {
-  BUCKET_HEAD = [
-    "user-agent": "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:139.0) Gecko/20100101 Firefox/139.0",
-    "accept": "*/*",
-    "accept-language": "en-US,en;q=0.5",
-    "x-amz-user-agent": "aws-sdk-js/2.1687.0",
-    "x-amz-s3-console-op-name": "HeadBucket",
-    "s3v3": "s3v3",
-    "origin": "https://us-east-1.console.aws.amazon.com",
-    "sec-gpc": "1",
-    "connection": "keep-alive",
-    "referer": "https://us-east-1.console.aws.amazon.com/",
-    "sec-fetch-dest": "empty",
-    "sec-fetch-mode": "cors",
-    "sec-fetch-site": "cross-site",
-  ]
+/// Anonymous client with the console headers, its connections reported to `timer`.
+fn client(timer: &ConnectTimer) -> Result<Client> {
+  Client::builder()
+    .default_headers(config::current().network.header_map())
+    .connector_layer(timer.clone())
+    .build()
 }
 
 #[inline(always)]
-async fn head(u: impl IntoUrl) -> Result<Response> {
+async fn head(u: impl IntoUrl, timer: &ConnectTimer) -> Result<Response> {
+  client(timer)?.head(u).send().await
+}
+
+/// Anonymous `GET` with the console headers, returning the status and body.
+async fn get_text(u: impl IntoUrl, timer: &ConnectTimer) -> Option<(StatusCode, String)> {
+  let response = client(timer).ok()?.get(u).send().await.ok()?;
+  let status = response.status();
+  Some((status, response.text().await.unwrap_or_default()))
+}
+
+/// The parts of an S3 `<Error>` document we care about.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct S3Error {
+  pub code: Option<Arc<str>>,
+  pub region: Option<Region>,
+}
+
+impl S3Error {
+  pub fn parse(body: &str) -> Self {
+    let region = xml_value(body, "Region")
+      .and_then(Region::from_name)
+      .or_else(|| xml_value(body, "Endpoint").and_then(Region::from_str_contains));
+    Self {
+      code: xml_value(body, "Code").map(Arc::from),
+      region,
+    }
+  }
+}
+
+/// `HEAD` responses carry no body, so the S3 error document needs a `GET` of the same resource.
+async fn s3_error(u: impl IntoUrl, timer: &ConnectTimer) -> Option<S3Error> {
+  let (_, body) = get_text(u, timer).await?;
+  Some(S3Error::parse(&body))
+}
+
+/// Response headers kept on a result for the details view.
+const HEADERS_OF_INTEREST: [&str; 5] = ["x-amz-bucket-region", "x-amz-request-id", "x-amz-id-2", "server", "location"];
+
+fn headers_of_interest(response: &Response) -> BTreeMap<&'static str, Arc<str>> {
+  HEADERS_OF_INTEREST
+    .into_iter()
+    .filter_map(|name| Some((name, Arc::from(response.headers().get(name)?.to_str().ok()?))))
+    .collect()
+}
+
+/// S3 sends `x-amz-bucket-region` on redirects, but also on 200, 403 and most 400 answers.
+fn header_region(response: &Response) -> Option<Region> {
+  Region::from_name(response.headers().get("x-amz-bucket-region")?.to_str().ok()?)
+}
+
+async fn signed(method: Method, u: Url, region: Region, credentials: &Credentials, timer: &ConnectTimer) -> Result<Response> {
+  let headers = SigV4::s3(credentials, region.as_ref()).headers(method.as_str(), &u);
   Client::builder()
-    .default_headers(HeaderMap::from_iter(BUCKET_HEAD))
+    .connector_layer(timer.clone())
     .build()?
-    .head(u)
+    .request(method, u)
+    .headers(headers)
     .send()
     .await
 }
 
+/// Signed `HeadBucket` of the path-style `bucket` url, so bucket names with dots keep a valid certificate.
+async fn head_bucket(bucket: Url, region: Region, credentials: &Credentials, timer: &ConnectTimer) -> Result<Response> {
+  signed(Method::HEAD, bucket, region, credentials, timer).await
+}
+
+/// Signed `GetBucketLocation`, only succeeds for buckets the credentials can read.
+async fn get_bucket_location(mut bucket: Url, region: Region, credentials: &Credentials, timer: &ConnectTimer) -> Result<Option<Region>> {
+  bucket.set_query(Some("location"));
+  let url = bucket;
+  let response = signed(Method::GET, url, region, credentials, timer).await?;
+  if !response.status().is_success() {
+    return Ok(None);
+  }
+
+  let body = response.text().await?;
+  Ok(xml_value(&body, "LocationConstraint").map(|location| match location {
+    "" => Region::UsEast1,
+    "EU" => Region::EuWest1,
+    location => Region::from(location),
+  }))
+}
+
+/// Text content of the first `<tag>` element, good enough for S3's flat XML bodies.
+pub fn xml_value<'a>(body: &'a str, tag: &str) -> Option<&'a str> {
+  let open = body.find(&format!("<{tag}"))?;
+  let rest = &body[open + tag.len() + 1..];
+  let end_of_open = rest.find('>')?;
+  if rest[..end_of_open].ends_with('/') {
+    return Some("");
+  }
+  let content = &rest[end_of_open + 1..];
+  let close = content.find(&format!("</{tag}>"))?;
+  Some(content[..close].trim())
+}
+
+/// What a name resolved to: every CNAME hop in answer order, then the first A record.
+#[derive(Debug, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize)]
+pub struct Resolution {
+  pub cnames: Vec<Arc<str>>,
+  pub ip: Option<Ipv4Addr>,
+}
+
+pub async fn resolve(name: &str) -> Resolution {
+  let Ok(domain) = addr::parse_domain_name(name) else {
+    return Resolution::default();
+  };
+  let Ok(lookup) = resolver().ipv4_lookup(domain.as_str()).await else {
+    return Resolution::default();
+  };
+
+  let records = lookup.as_lookup().records();
+  Resolution {
+    cnames: records
+      .iter()
+      .filter_map(|record| record.data()?.as_cname())
+      .map(|cname| Arc::from(cname.0.to_string().trim_end_matches('.')))
+      .collect(),
+    ip: records.iter().find_map(|record| record.data()?.as_a()).map(|a| a.0),
+  }
+}
+
 pub async fn get_ip(domain: addr::domain::Name<'_>) -> Option<Ipv4Addr> {
-  let lookup = TAR.ipv4_lookup(domain.as_str()).await.ok()?;
+  let lookup = resolver().ipv4_lookup(domain.as_str()).await.ok()?;
   let a = lookup.as_lookup().records().iter().find(|r| r.data().is_some_and(|d| d.is_a()))?;
   Some(a.data()?.as_a()?.0)
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_xml_value() {
+    let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LocationConstraint xmlns="http://s3.amazonaws.com/doc/2006-03-01/">eu-west-2</LocationConstraint>"#;
+    assert_eq!(xml_value(body, "LocationConstraint"), Some("eu-west-2"));
+
+    let empty = r#"<LocationConstraint xmlns="http://s3.amazonaws.com/doc/2006-03-01/"/>"#;
+    assert_eq!(xml_value(empty, "LocationConstraint"), Some(""));
+
+    assert_eq!(xml_value("<Error><Code>NoSuchBucket</Code></Error>", "Code"), Some("NoSuchBucket"));
+    assert_eq!(xml_value("<Error></Error>", "Code"), None);
+  }
+
+  #[test]
+  fn test_s3_error() {
+    let malformed = "<Error><Code>AuthorizationHeaderMalformed</Code><Region>eu-west-3</Region></Error>";
+    let error = S3Error::parse(malformed);
+    assert_eq!(error.code.as_deref(), Some("AuthorizationHeaderMalformed"));
+    assert_eq!(error.region, Some(Region::EuWest3));
+
+    let redirect = "<Error><Code>PermanentRedirect</Code><Endpoint>bucket.s3.ap-south-1.amazonaws.com</Endpoint></Error>";
+    assert_eq!(S3Error::parse(redirect).region, Some(Region::ApSouth1));
+  }
+}
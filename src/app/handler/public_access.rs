@@ -0,0 +1,100 @@
+use ratatui::prelude::Stylize;
+use ratatui::style::Color;
+use ratatui::text::Span;
+use serde::Serialize;
+
+use super::*;
+
+/// Findings of the second-phase probe, every check is anonymous.
+#[derive(Default, Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Serialize)]
+pub struct PublicAccess {
+  /// `ListObjectsV2` with `max-keys=1` succeeded.
+  pub listable: bool,
+  /// `GetBucketAcl` succeeded, so the ACL itself is readable by `AllUsers`.
+  pub acl_readable: bool,
+  /// `GetBucketPolicyStatus` reported `IsPublic`.
+  pub policy_public: bool,
+  /// The static website endpoint answered with content.
+  pub website: bool,
+}
+
+impl PublicAccess {
+  /// Regions that still serve websites from `s3-website-<region>` instead of `s3-website.<region>`.
+  const DASH_WEBSITE_REGIONS: [Region; 8] = [
+    Region::UsEast1,
+    Region::UsWest1,
+    Region::UsWest2,
+    Region::ApSoutheast1,
+    Region::ApSoutheast2,
+    Region::ApNortheast1,
+    Region::EuWest1,
+    Region::SaEast1,
+  ];
+
+  pub async fn probe(name: &str, region: Region) -> Self {
+    let base = format!("https://s3.{region}.amazonaws.com/{name}");
+    // The calls overlap, their connection setup stays part of the public phase.
+    let timer = ConnectTimer::default();
+    let (listable, acl, policy, website) = tokio::join!(
+      get_text(format!("{base}?list-type=2&max-keys=1"), &timer),
+      get_text(format!("{base}?acl"), &timer),
+      get_text(format!("{base}?policyStatus"), &timer),
+      get_text(Self::website_url(name, region), &timer),
+    );
+
+    Self {
+      listable: listable.is_some_and(|(status, _)| status.is_success()),
+      acl_readable: acl.is_some_and(|(status, _)| status.is_success()),
+      policy_public: policy.is_some_and(|(status, body)| status.is_success() && xml_value(&body, "IsPublic") == Some("true")),
+      website: website.is_some_and(|(status, _)| status.is_success()),
+    }
+  }
+
+  fn website_url(name: &str, region: Region) -> String {
+    if Self::DASH_WEBSITE_REGIONS.contains(&region) {
+      format!("http://{name}.s3-website-{region}.amazonaws.com/")
+    } else {
+      format!("http://{name}.s3-website.{region}.amazonaws.com/")
+    }
+  }
+
+  pub fn is_public(&self) -> bool {
+    self.listable || self.acl_readable || self.policy_public || self.website
+  }
+
+  fn flags(&self) -> impl Iterator<Item = (&'static str, &'static str)> {
+    [
+      (self.listable, "📂", "list"),
+      (self.acl_readable, "🔓", "acl"),
+      (self.policy_public, "📜", "policy"),
+      (self.website, "🌐", "website"),
+    ]
+    .into_iter()
+    .filter_map(|(set, badge, name)| set.then_some((badge, name)))
+  }
+
+  pub fn badges(&self) -> Vec<Span<'static>> {
+    self.flags().map(|(badge, _)| Span::raw(badge).fg(Color::Yellow)).collect()
+  }
+
+  pub fn names(&self) -> Vec<&'static str> {
+    self.flags().map(|(_, name)| name).collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_website_url() {
+    assert_eq!(
+      PublicAccess::website_url("bucket", Region::UsEast1),
+      "http://bucket.s3-website-us-east-1.amazonaws.com/"
+    );
+    assert_eq!(
+      PublicAccess::website_url("bucket", Region::EuCentral1),
+      "http://bucket.s3-website.eu-central-1.amazonaws.com/"
+    );
+  }
+}
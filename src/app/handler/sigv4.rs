@@ -0,0 +1,191 @@
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::Url;
+use reqwest::header::{AUTHORIZATION, HOST, HeaderMap, HeaderName, HeaderValue};
+use sha2::{Digest, Sha256};
+
+use super::Credentials;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+/// `hex(sha256(""))`, every probe we send has an empty body.
+pub const EMPTY_PAYLOAD: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+pub struct SigV4<'a> {
+  credentials: &'a Credentials,
+  region: &'a str,
+  service: &'a str,
+  time: DateTime<Utc>,
+}
+
+impl<'a> SigV4<'a> {
+  pub fn new(credentials: &'a Credentials, region: &'a str, service: &'a str, time: DateTime<Utc>) -> Self {
+    Self {
+      credentials,
+      region,
+      service,
+      time,
+    }
+  }
+
+  pub fn s3(credentials: &'a Credentials, region: &'a str) -> Self {
+    Self::new(credentials, region, "s3", Utc::now())
+  }
+
+  fn amz_date(&self) -> String {
+    self.time.format("%Y%m%dT%H%M%SZ").to_string()
+  }
+
+  fn date_stamp(&self) -> String {
+    self.time.format("%Y%m%d").to_string()
+  }
+
+  fn scope(&self) -> String {
+    format!("{}/{}/{}/aws4_request", self.date_stamp(), self.region, self.service)
+  }
+
+  /// Headers required by S3 for a signed request with an empty payload.
+  pub fn headers(&self, method: &str, url: &Url) -> HeaderMap {
+    let mut headers = vec![
+      ("host", url.host_str().unwrap_or_default().to_string()),
+      ("x-amz-content-sha256", EMPTY_PAYLOAD.to_string()),
+      ("x-amz-date", self.amz_date()),
+    ];
+    if let Some(token) = self.credentials.session_token() {
+      headers.push(("x-amz-security-token", token.to_string()));
+    }
+
+    let authorization = self.authorization(method, url, &headers, EMPTY_PAYLOAD);
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+      if let Ok(value) = HeaderValue::from_str(&value) {
+        map.insert(HeaderName::from_static(name), value);
+      }
+    }
+    map.remove(HOST);
+    if let Ok(value) = HeaderValue::from_str(&authorization) {
+      map.insert(AUTHORIZATION, value);
+    }
+
+    map
+  }
+
+  /// `headers` must be lowercase names, and include every header that will be signed.
+  pub fn authorization(&self, method: &str, url: &Url, headers: &[(&str, String)], payload_hash: &str) -> String {
+    let mut headers = headers.iter().map(|(name, value)| (*name, value.trim())).collect::<Vec<_>>();
+    headers.sort_by_key(|(name, _)| *name);
+
+    let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+    let canonical_headers = headers.iter().map(|(name, value)| format!("{name}:{value}\n")).collect::<String>();
+    let canonical_request = [
+      method,
+      canonical_uri(url),
+      &canonical_query(url),
+      &canonical_headers,
+      &signed_headers,
+      payload_hash,
+    ]
+    .join("\n");
+
+    let string_to_sign = [
+      ALGORITHM,
+      &self.amz_date(),
+      &self.scope(),
+      &hex::encode(Sha256::digest(canonical_request.as_bytes())),
+    ]
+    .join("\n");
+    let signature = hex::encode(hmac(&self.signing_key(), string_to_sign.as_bytes()));
+
+    format!(
+      "{ALGORITHM} Credential={}/{}, SignedHeaders={signed_headers}, Signature={signature}",
+      self.credentials.access_key_id(),
+      self.scope()
+    )
+  }
+
+  fn signing_key(&self) -> Vec<u8> {
+    let secret = format!("AWS4{}", self.credentials.secret_access_key());
+    let date = hmac(secret.as_bytes(), self.date_stamp().as_bytes());
+    let region = hmac(&date, self.region.as_bytes());
+    let service = hmac(&region, self.service.as_bytes());
+    hmac(&service, b"aws4_request")
+  }
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+  let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any size");
+  mac.update(data);
+  mac.finalize().into_bytes().to_vec()
+}
+
+fn canonical_uri(url: &Url) -> &str {
+  match url.path() {
+    "" => "/",
+    path => path,
+  }
+}
+
+fn canonical_query(url: &Url) -> String {
+  let mut pairs = url
+    .query_pairs()
+    .map(|(key, value)| (uri_encode(&key), uri_encode(&value)))
+    .collect::<Vec<_>>();
+  pairs.sort();
+
+  pairs
+    .into_iter()
+    .map(|(key, value)| format!("{key}={value}"))
+    .collect::<Vec<_>>()
+    .join("&")
+}
+
+fn uri_encode(value: &str) -> String {
+  let mut encoded = String::with_capacity(value.len());
+  for byte in value.bytes() {
+    match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+      _ => encoded.push_str(&format!("%{byte:02X}")),
+    }
+  }
+  encoded
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::TimeZone;
+
+  use super::*;
+
+  // Example request from the AWS "Create a signed AWS API request" guide.
+  #[test]
+  fn test_signature_example() {
+    let credentials = Credentials::new("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY", None);
+    let time = Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap();
+    let signer = SigV4::new(&credentials, "us-east-1", "iam", time);
+    let url = Url::parse("https://iam.amazonaws.com/?Action=ListUsers&Version=2010-05-08").unwrap();
+    let headers = [
+      ("content-type", "application/x-www-form-urlencoded; charset=utf-8".to_string()),
+      ("host", "iam.amazonaws.com".to_string()),
+      ("x-amz-date", "20150830T123600Z".to_string()),
+    ];
+
+    assert_eq!(
+      hex::encode(signer.signing_key()),
+      "c4afb1cc5771d871763a393e44b703571b55cc28424d1a5e86da6ed3c154a4b9"
+    );
+    assert_eq!(
+      signer.authorization("GET", &url, &headers, EMPTY_PAYLOAD),
+      "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/iam/aws4_request, \
+       SignedHeaders=content-type;host;x-amz-date, \
+       Signature=5d672d79c15b13162d9279b0855cfba6789a8edb4c82c400e06b5924a6f2b5d7"
+    );
+  }
+
+  #[test]
+  fn test_canonical_query() {
+    let url = Url::parse("https://s3.us-east-1.amazonaws.com/bucket?location").unwrap();
+    assert_eq!(canonical_query(&url), "location=");
+    assert_eq!(uri_encode("a b/c"), "a%20b%2Fc");
+  }
+}
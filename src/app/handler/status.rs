@@ -0,0 +1,151 @@
+use std::error::Error as StdError;
+
+use Status::*;
+use ratatui::prelude::Stylize;
+use ratatui::style::Color;
+use ratatui::text::Span;
+use serde::Serialize;
+use strum::{Display, EnumIs};
+
+use super::*;
+
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Hash, Display, EnumIs, Serialize)]
+pub enum Status {
+  Available,
+  Exists,
+  ExistsPublic,
+  WrongRegion,
+  InvalidName,
+  Throttled,
+  DnsFailure,
+  Timeout,
+  TlsError,
+  /// `0` when the request never got a response.
+  #[strum(to_string = "Unknown({0})")]
+  Unknown(u16),
+}
+
+impl Default for Status {
+  fn default() -> Self {
+    Unknown(0)
+  }
+}
+
+impl Status {
+  /// S3 error codes win over the HTTP status, they are the only way to tell several 400s apart.
+  pub fn classify(code: StatusCode, error_code: Option<&str>) -> Self {
+    match error_code {
+      Some("NoSuchBucket") => return Available,
+      Some("InvalidBucketName") => return InvalidName,
+      Some("AccessDenied" | "AllAccessDisabled") => return Exists,
+      Some("PermanentRedirect" | "TemporaryRedirect" | "AuthorizationHeaderMalformed" | "IllegalLocationConstraintException") => {
+        return WrongRegion;
+      }
+      Some("SlowDown" | "Throttling" | "RequestLimitExceeded" | "TooManyRequests") => return Throttled,
+      _ => {}
+    }
+
+    match code.as_u16() {
+      200..=299 => Exists,
+      301 | 307 => WrongRegion,
+      403 => Exists,
+      404 => Available,
+      429 | 503 => Throttled,
+      code => Unknown(code),
+    }
+  }
+
+  /// Walks the source chain, reqwest only tells us about timeouts and connect failures directly.
+  pub fn from_error(error: &Error) -> Self {
+    if error.is_timeout() {
+      return Timeout;
+    }
+
+    let mut chain = Vec::new();
+    let mut source: Option<&dyn StdError> = Some(error);
+    while let Some(error) = source {
+      chain.push(error.to_string().to_lowercase());
+      source = error.source();
+    }
+    let mentions = |needles: &[&str]| chain.iter().any(|message| needles.iter().any(|needle| message.contains(needle)));
+
+    if mentions(&["dns", "failed to lookup", "no record found", "name or service not known"]) {
+      DnsFailure
+    } else if mentions(&["certificate", "tls", "ssl", "handshake"]) {
+      TlsError
+    } else if mentions(&["timed out", "deadline"]) {
+      Timeout
+    } else {
+      Unknown(0)
+    }
+  }
+
+  pub fn is_taken(&self) -> bool {
+    matches!(self, Exists | ExistsPublic)
+  }
+
+  /// Anything that says nothing about the bucket itself.
+  pub fn is_error(&self) -> bool {
+    matches!(self, Throttled | DnsFailure | Timeout | TlsError | Unknown(_))
+  }
+
+  pub fn icon(&self) -> &'static str {
+    match self {
+      Available => "✅",
+      Exists => "⛔",
+      ExistsPublic => "🚨",
+      WrongRegion => "🧭",
+      InvalidName => "🚫",
+      Throttled => "🐢",
+      DnsFailure => "📡",
+      Timeout => "⌛",
+      TlsError => "🔒",
+      Unknown(_) => "❔",
+    }
+  }
+
+  pub fn color(&self) -> Color {
+    match self {
+      Available => Color::Green,
+      Exists => Color::Red,
+      ExistsPublic => Color::Magenta,
+      WrongRegion => Color::Cyan,
+      InvalidName => Color::DarkGray,
+      Throttled => Color::Yellow,
+      DnsFailure | Timeout | TlsError => Color::LightRed,
+      Unknown(_) => Color::Gray,
+    }
+  }
+}
+
+impl From<StatusCode> for Status {
+  fn from(status: StatusCode) -> Self {
+    Self::classify(status, None)
+  }
+}
+
+impl From<Status> for Span<'static> {
+  fn from(value: Status) -> Self {
+    Span::raw(value.icon()).fg(value.color())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_classify() {
+    assert_eq!(Status::classify(StatusCode::NOT_FOUND, None), Available);
+    assert_eq!(Status::classify(StatusCode::FORBIDDEN, None), Exists);
+    assert_eq!(Status::classify(StatusCode::MOVED_PERMANENTLY, None), WrongRegion);
+    assert_eq!(Status::classify(StatusCode::SERVICE_UNAVAILABLE, None), Throttled);
+    assert_eq!(Status::classify(StatusCode::INTERNAL_SERVER_ERROR, None), Unknown(500));
+    assert_eq!(Status::classify(StatusCode::BAD_REQUEST, Some("InvalidBucketName")), InvalidName);
+    assert_eq!(
+      Status::classify(StatusCode::BAD_REQUEST, Some("AuthorizationHeaderMalformed")),
+      WrongRegion
+    );
+    assert_eq!(Status::classify(StatusCode::BAD_REQUEST, None), Unknown(400));
+  }
+}
@@ -0,0 +1,136 @@
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+use serde::{Serialize, Serializer};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Wall time spent in each phase of a probe.
+#[derive(Default, Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Serialize)]
+pub struct Timings {
+  /// Resolving the bucket name, done to guess the region from AWS ranges.
+  #[serde(serialize_with = "millis")]
+  pub dns: Duration,
+  /// Opening the probe's own connections, resolution, TCP and TLS, `None` when none was opened.
+  #[serde(serialize_with = "opt_millis")]
+  pub connect: Option<Duration>,
+  /// Every HTTP call of the probe, connection setup excluded.
+  #[serde(serialize_with = "millis")]
+  pub request: Duration,
+  #[serde(serialize_with = "millis")]
+  pub total: Duration,
+}
+
+impl Timings {
+  /// One HTTP call that took `elapsed`, what `timer` saw of it goes to `connect` instead of `request`.
+  pub fn add_request(&mut self, elapsed: Duration, timer: &ConnectTimer) {
+    let connect = timer.take();
+    if let Some(connect) = connect {
+      self.connect = Some(self.connect.unwrap_or_default() + connect);
+    }
+    self.request += elapsed.saturating_sub(connect.unwrap_or_default());
+  }
+}
+
+/// Connector layer adding up how long a client spent opening connections.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectTimer(Arc<Mutex<Option<Duration>>>);
+
+impl ConnectTimer {
+  /// Time spent since the last call, `None` when no connection was opened.
+  pub fn take(&self) -> Option<Duration> {
+    self.0.lock().unwrap().take()
+  }
+}
+
+impl<S> Layer<S> for ConnectTimer {
+  type Service = TimedConnector<S>;
+
+  fn layer(&self, inner: S) -> Self::Service {
+    TimedConnector { inner, timer: self.clone() }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct TimedConnector<S> {
+  inner: S,
+  timer: ConnectTimer,
+}
+
+impl<S: Service<R>, R> Service<R> for TimedConnector<S>
+where
+  S::Future: Send + 'static,
+{
+  type Response = S::Response;
+  type Error = S::Error;
+  type Future = BoxFuture<'static, Result<S::Response, S::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self.inner.poll_ready(cx)
+  }
+
+  fn call(&mut self, request: R) -> Self::Future {
+    let timer = self.timer.clone();
+    let started = Instant::now();
+    let connecting = self.inner.call(request);
+    Box::pin(async move {
+      let connection = connecting.await;
+      if connection.is_ok() {
+        let mut spent = timer.0.lock().unwrap();
+        *spent = Some(spent.unwrap_or_default() + started.elapsed());
+      }
+      connection
+    })
+  }
+}
+
+pub async fn timed<F: Future>(future: F) -> (Duration, F::Output) {
+  let started = Instant::now();
+  let output = future.await;
+  (started.elapsed(), output)
+}
+
+fn millis<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+  serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
+
+fn opt_millis<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+  match duration {
+    Some(duration) => millis(duration, serializer),
+    None => serializer.serialize_none(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use tokio::io::{AsyncReadExt, AsyncWriteExt};
+  use tokio::net::TcpListener;
+
+  use super::*;
+
+  #[tokio::test]
+  async fn test_connect_timer() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+      let (mut stream, _) = listener.accept().await.unwrap();
+      let mut request = [0; 1024];
+      _ = stream.read(&mut request).await.unwrap();
+      stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").await.unwrap();
+    });
+
+    let timer = ConnectTimer::default();
+    let client = reqwest::Client::builder().connector_layer(timer.clone()).build().unwrap();
+    let (elapsed, response) = timed(client.head(format!("http://{address}/")).send()).await;
+    assert!(response.unwrap().status().is_success());
+
+    let mut timings = Timings::default();
+    timings.add_request(elapsed, &timer);
+    let connect = timings.connect.unwrap();
+    assert!(connect <= elapsed);
+    assert_eq!(timings.request, elapsed - connect);
+    assert_eq!(timer.take(), None);
+  }
+}
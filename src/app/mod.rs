@@ -9,7 +9,10 @@ type MpscTx<T> = mpsc::Sender<T>;
 pub mod handler;
 
 mod app_;
+mod export;
 mod exports;
 mod popup;
 mod scroll_states;
+mod settings;
 mod state;
+mod throughput;
@@ -1,36 +1,122 @@
+use std::time::Duration;
+
 use crossterm::event::Event;
+use futures::FutureExt;
+use futures::future::BoxFuture;
 use helper::UnhandledEvent;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::prelude::Widget;
-use ratatui::widgets::Paragraph;
 
 use crate::ui::{clear, fix_center};
-use crate::widgets::{Alert, Confirmation, Input};
+use crate::widgets::{Alert, Check, Confirmation, Details, FilePicker, Form, Input, Load, Loaded, Severity, Verdict};
+
+mod stack;
+pub use stack::*;
 
 pub enum Popup {
-  Input(Input),
+  Input(Box<Input>),
+  FilePicker(FilePicker),
+  Form(Form),
   Confirmation(Confirmation),
-  Warning(Paragraph<'static>),
+  /// An `Alert` that dismisses itself after `WARNING_TIMEOUT`.
+  Warning(Alert),
   Alert(Alert),
+  Details(Details),
+}
+
+/// Work a popup hands out to run on its own task, the result comes back under the same ticket.
+pub struct Job {
+  pub ticket: u64,
+  pub done: BoxFuture<'static, Done>,
+}
+
+/// What a `Job` came back with.
+pub enum Done {
+  Verdict(Verdict),
+  Loaded(Loaded),
 }
 
 impl Popup {
+  pub const WARNING_TIMEOUT: Duration = Duration::from_secs(8);
+
+  pub fn warning(title: &str, content: impl Iterator<Item = String>) -> Self {
+    Popup::Warning(
+      Alert::new(title, content)
+        .severity(Severity::Warning)
+        .dismiss_after(Self::WARNING_TIMEOUT),
+    )
+  }
+
+  pub fn error(title: &str, content: impl Iterator<Item = String>) -> Self {
+    Popup::Alert(Alert::new(title, content).severity(Severity::Error))
+  }
+
+  pub fn is_expired(&self) -> bool {
+    match self {
+      Popup::Warning(alert) | Popup::Alert(alert) => alert.is_expired(),
+      _ => false,
+    }
+  }
+
+  /// Work missing for what the popup shows: checks of the validated fields, reads of the file picker.
+  pub fn jobs(&mut self) -> Vec<Job> {
+    let check = |Check { ticket, verdict }| Job {
+      ticket,
+      done: verdict.map(Done::Verdict).boxed(),
+    };
+    let load = |Load { ticket, loaded }| Job {
+      ticket,
+      done: loaded.map(Done::Loaded).boxed(),
+    };
+    match self {
+      Popup::Input(input) => input.validation().into_iter().map(check).collect(),
+      Popup::Form(form) => form.validations().into_iter().map(check).collect(),
+      Popup::FilePicker(picker) => picker.loads().into_iter().map(load).collect(),
+      _ => Vec::new(),
+    }
+  }
+
+  pub fn awaits(&self, ticket: u64) -> bool {
+    match self {
+      Popup::Input(input) => input.awaits(ticket),
+      Popup::Form(form) => form.awaits(ticket),
+      Popup::FilePicker(picker) => picker.awaits(ticket),
+      _ => false,
+    }
+  }
+
+  /// Hands a result back to whatever asked for it, stale ones are dropped.
+  pub fn deliver(&mut self, ticket: u64, done: Done) -> bool {
+    match (self, done) {
+      (Popup::Input(input), Done::Verdict(verdict)) => input.deliver(ticket, verdict),
+      (Popup::Form(form), Done::Verdict(verdict)) => form.deliver(ticket, verdict),
+      (Popup::FilePicker(picker), Done::Loaded(loaded)) => picker.deliver(ticket, loaded),
+      _ => false,
+    }
+  }
+
   pub fn handle_event(&mut self, event: &Event) -> Option<UnhandledEvent> {
     match *self {
       Popup::Input(ref mut input) => input.handle_event(event),
-      Popup::Confirmation(_) => todo!(),
-      Popup::Warning(_) => todo!(),
+      Popup::FilePicker(ref mut picker) => picker.handle_event(event),
+      Popup::Form(ref mut form) => form.handle_event(event),
+      Popup::Confirmation(ref mut confirmation) => confirmation.handle_event(event),
+      Popup::Warning(ref mut alert) => alert.handle_event(event),
       Popup::Alert(ref mut alert) => alert.handle_event(event),
+      Popup::Details(ref mut details) => details.handle_event(event),
     }
   }
 
   pub fn area(&self, area: Rect) -> Rect {
     match *self {
       Popup::Input(_) => fix_center(area, 65, 25),
-      Popup::Confirmation(_) => fix_center(area, 45, 25),
-      Popup::Warning(_) => fix_center(area, 15, 15),
+      Popup::FilePicker(_) => fix_center(area, 70, 22),
+      Popup::Form(ref form) => fix_center(area, 60, form.height()),
+      Popup::Confirmation(_) => fix_center(area, 50, 7),
+      Popup::Warning(_) => fix_center(area, 40, 8),
       Popup::Alert(_) => fix_center(area, 25, 25),
+      Popup::Details(_) => fix_center(area, 80, 20),
     }
   }
 }
@@ -45,9 +131,12 @@ impl Widget for &Popup {
 
     match self {
       Popup::Input(widget) => widget.render(area, buf),
+      Popup::FilePicker(widget) => widget.render(area, buf),
+      Popup::Form(widget) => widget.render(area, buf),
       Popup::Confirmation(widget) => widget.render(area, buf),
       Popup::Warning(widget) => widget.render(area, buf),
       Popup::Alert(widget) => widget.render(area, buf),
+      Popup::Details(widget) => widget.render(area, buf),
     }
   }
 }
@@ -0,0 +1,83 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::Widget;
+
+use super::{Done, Popup};
+
+/// Modal layers, only the top one receives events. Each layer carries what its result is for.
+pub struct PopupStack<I> {
+  layers: Vec<(Popup, I)>,
+}
+
+impl<I> Default for PopupStack<I> {
+  fn default() -> Self {
+    Self { layers: Vec::new() }
+  }
+}
+
+impl<I> PopupStack<I> {
+  pub fn push(&mut self, popup: Popup, intent: I) {
+    self.layers.push((popup, intent));
+  }
+
+  pub fn pop(&mut self) -> Option<(Popup, I)> {
+    self.layers.pop()
+  }
+
+  pub fn top(&self) -> Option<(&Popup, &I)> {
+    self.layers.last().map(|(popup, intent)| (popup, intent))
+  }
+
+  pub fn top_mut(&mut self) -> Option<&mut Popup> {
+    self.layers.last_mut().map(|(popup, _)| popup)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.layers.is_empty()
+  }
+
+  pub fn len(&self) -> usize {
+    self.layers.len()
+  }
+
+  /// Hands a job result to the layer awaiting it, wherever it is in the stack.
+  pub fn deliver(&mut self, ticket: u64, done: Done) -> bool {
+    let awaiting = self.layers.iter_mut().find(|(popup, _)| popup.awaits(ticket));
+    awaiting.is_some_and(|(popup, _)| popup.deliver(ticket, done))
+  }
+
+  /// Timed popups can expire under another layer, they leave the stack all the same.
+  pub fn expire(&mut self) {
+    self.layers.retain(|(popup, _)| !popup.is_expired());
+  }
+}
+
+impl<I> Widget for &PopupStack<I> {
+  fn render(self, area: Rect, buf: &mut Buffer)
+  where
+    Self: Sized,
+  {
+    for (popup, _) in &self.layers {
+      popup.render(area, buf);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::widgets::Alert;
+
+  #[test]
+  fn test_push_pop() {
+    let mut stack = PopupStack::default();
+    stack.push(Popup::Alert(Alert::new("first", std::iter::empty::<String>())), 1);
+    stack.push(Popup::warning("second", std::iter::empty()), 2);
+    assert_eq!(stack.top().map(|(_, intent)| *intent), Some(2));
+
+    assert!(matches!(stack.pop(), Some((Popup::Warning(_), 2))));
+    assert_eq!(stack.len(), 1);
+    stack.expire();
+    assert_eq!(stack.len(), 1);
+  }
+}
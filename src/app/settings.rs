@@ -0,0 +1,162 @@
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer};
+use strum::{Display, VariantArray};
+
+use crate::app::handler::{BucketStatus, Provider, Region};
+use crate::widgets::{Field, Form, Input, RegionCompleter, ValidRegion};
+
+/// Live pipeline configuration, shared with the background tasks through a watch channel.
+/// Starts from the `[run]` table of the configuration file.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+  /// Run the public access probe on buckets that are already taken.
+  pub public_probe: bool,
+  /// Buckets checked at the same time.
+  pub concurrency: usize,
+  /// Checks started per second, `0` for no limit.
+  pub rate_limit: u32,
+  pub output_format: OutputFormat,
+  pub provider: Provider,
+  /// A check taking longer is dropped and logged as an error, in seconds in the file.
+  #[serde(deserialize_with = "seconds")]
+  pub timeout: Duration,
+  /// First region asked when the bucket address isn't in a known AWS range.
+  pub default_region: Region,
+}
+
+impl Default for Settings {
+  fn default() -> Self {
+    Self {
+      public_probe: false,
+      concurrency: 1,
+      rate_limit: 0,
+      output_format: OutputFormat::default(),
+      provider: Provider::default(),
+      timeout: Duration::from_secs(30),
+      default_region: Region::UsEast1,
+    }
+  }
+}
+
+impl Settings {
+  /// The "Run settings" popup, filled with the current values.
+  pub fn form(&self) -> Form {
+    let region = Input::new(" Default region ".to_string(), "us-east-1".to_string())
+      .with_text(self.default_region.as_ref())
+      .completer(RegionCompleter)
+      .validator(ValidRegion);
+    Form::new("Run settings")
+      .field(Field::spinner("concurrency", "Concurrency", self.concurrency as i64, 1..=64, 1))
+      .field(Field::spinner("rate_limit", "Rate limit", self.rate_limit as i64, 0..=1000, 5).unit(" checks/s, 0 = off"))
+      .field(Field::spinner("timeout", "Timeout", self.timeout.as_secs() as i64, 1..=300, 5).unit("s"))
+      .field(Field::select(
+        "provider",
+        "Provider",
+        Provider::VARIANTS,
+        index_of(Provider::VARIANTS, &self.provider),
+      ))
+      .field(Field::select(
+        "output_format",
+        "Output format",
+        OutputFormat::VARIANTS,
+        index_of(OutputFormat::VARIANTS, &self.output_format),
+      ))
+      .field(Field::toggle("public_probe", "Public probe", self.public_probe))
+      .field(Field::text("default_region", region))
+  }
+
+  /// Takes the values of a submitted `form`, fields it doesn't have keep their value.
+  pub fn update(&mut self, form: &Form) {
+    if let Some(concurrency) = form.number("concurrency") {
+      self.concurrency = concurrency.max(1) as usize;
+    }
+    if let Some(rate_limit) = form.number("rate_limit") {
+      self.rate_limit = rate_limit.max(0) as u32;
+    }
+    if let Some(timeout) = form.number("timeout") {
+      self.timeout = Duration::from_secs(timeout.max(1) as u64);
+    }
+    if let Some(provider) = form.choice("provider").and_then(|index| Provider::VARIANTS.get(index)) {
+      self.provider = *provider;
+    }
+    if let Some(format) = form.choice("output_format").and_then(|index| OutputFormat::VARIANTS.get(index)) {
+      self.output_format = *format;
+    }
+    if let Some(public_probe) = form.toggle("public_probe") {
+      self.public_probe = public_probe;
+    }
+    if let Some(region) = form.text("default_region").and_then(Region::from_name) {
+      self.default_region = region;
+    }
+  }
+}
+
+fn index_of<T: PartialEq>(variants: &[T], value: &T) -> usize {
+  variants.iter().position(|variant| variant == value).unwrap_or_default()
+}
+
+fn seconds<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+  u64::deserialize(deserializer).map(Duration::from_secs)
+}
+
+/// How results are written to the output file.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Display, VariantArray, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+  /// JSON lines for `.json`/`.jsonl`/`.ndjson` outputs, plain lines otherwise.
+  #[default]
+  #[strum(to_string = "By extension")]
+  Auto,
+  Text,
+  #[strum(to_string = "JSON lines")]
+  Json,
+}
+
+impl OutputFormat {
+  /// What `Auto` stands for with this output.
+  pub fn resolve(self, path: &Path) -> Self {
+    match (self, path.extension().and_then(|ext| ext.to_str())) {
+      (Self::Auto, Some("json" | "jsonl" | "ndjson")) => Self::Json,
+      (Self::Auto, _) => Self::Text,
+      (format, _) => format,
+    }
+  }
+
+  pub fn format(&self, bucket: &BucketStatus) -> String {
+    match self {
+      Self::Json => serde_json::to_string(bucket).unwrap_or_else(|_| bucket.to_string()),
+      Self::Auto | Self::Text => bucket.to_string(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::PathBuf;
+
+  use super::*;
+
+  #[test]
+  fn test_resolve() {
+    let output = PathBuf::from("buckets.jsonl");
+    assert_eq!(OutputFormat::Auto.resolve(&output), OutputFormat::Json);
+    assert_eq!(OutputFormat::Text.resolve(&output), OutputFormat::Text);
+    assert_eq!(OutputFormat::Auto.resolve(&PathBuf::from("buckets.txt")), OutputFormat::Text);
+  }
+
+  #[test]
+  fn test_form_round_trip() {
+    let settings = Settings {
+      concurrency: 8,
+      provider: Provider::Anonymous,
+      default_region: Region::EuWest2,
+      ..Default::default()
+    };
+    let mut updated = Settings::default();
+    updated.update(&settings.form());
+    assert_eq!(updated, settings);
+  }
+}
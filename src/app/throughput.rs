@@ -0,0 +1,160 @@
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+
+/// Pause-aware run clock with an exponentially weighted items/second rate.
+#[derive(Debug, Default, Clone)]
+pub struct Clock {
+  started: Option<Instant>,
+  paused_at: Option<Instant>,
+  finished_at: Option<Instant>,
+  paused: Duration,
+  rate: Option<f64>,
+  window_start: Option<Instant>,
+  window_count: usize,
+}
+
+impl Clock {
+  /// Weight of the newest one-second sample.
+  const ALPHA: f64 = 0.3;
+  const WINDOW: Duration = Duration::from_secs(1);
+
+  pub fn resume(&mut self) {
+    let now = Instant::now();
+    if self.started.is_none() {
+      self.started = Some(now);
+    }
+    if let Some(paused_at) = self.paused_at.take() {
+      self.paused += now - paused_at;
+    }
+    // Time spent paused must not dilute the next sample.
+    self.window_start = Some(now);
+    self.window_count = 0;
+  }
+
+  pub fn pause(&mut self) {
+    if self.started.is_some() && self.paused_at.is_none() && self.finished_at.is_none() {
+      self.paused_at = Some(Instant::now());
+    }
+  }
+
+  pub fn finish(&mut self) {
+    self.finished_at.get_or_insert_with(Instant::now);
+  }
+
+  pub fn is_paused(&self) -> bool {
+    self.paused_at.is_some()
+  }
+
+  pub fn tick(&mut self) {
+    let now = Instant::now();
+    let window_start = *self.window_start.get_or_insert(now);
+    self.window_count += 1;
+
+    let window = now - window_start;
+    if window >= Self::WINDOW {
+      self.rate = Some(self.sample(self.window_count, window));
+      self.window_start = Some(now);
+      self.window_count = 0;
+    }
+  }
+
+  fn sample(&self, count: usize, window: Duration) -> f64 {
+    let sample = count as f64 / window.as_secs_f64();
+    match self.rate {
+      Some(rate) => Self::ALPHA * sample + (1.0 - Self::ALPHA) * rate,
+      None => sample,
+    }
+  }
+
+  /// A window that ran long without items pulls the rate down instead of keeping it stale.
+  pub fn rate(&self) -> f64 {
+    if self.is_paused() || self.finished_at.is_some() {
+      return self.rate.unwrap_or_default();
+    }
+
+    match self.window_start {
+      Some(window_start) if window_start.elapsed() >= Self::WINDOW => self.sample(self.window_count, window_start.elapsed()),
+      _ => self.rate.unwrap_or_default(),
+    }
+  }
+
+  pub fn elapsed(&self) -> Duration {
+    let Some(started) = self.started else {
+      return Duration::ZERO;
+    };
+    let end = self.finished_at.or(self.paused_at).unwrap_or_else(Instant::now);
+    end.saturating_duration_since(started).saturating_sub(self.paused)
+  }
+}
+
+/// Snapshot of a run, cheap to take from any task.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Progress {
+  pub current: usize,
+  pub max: usize,
+  pub elapsed: Duration,
+  /// Items per second.
+  pub rate: f64,
+  pub paused: bool,
+}
+
+impl Progress {
+  pub fn percentage(&self) -> f64 {
+    if self.max == 0 {
+      0.0
+    } else {
+      (self.current as f64 / self.max as f64 * 100.0).min(100.0)
+    }
+  }
+
+  /// `None` until there is a rate to extrapolate from.
+  pub fn eta(&self) -> Option<Duration> {
+    let remaining = self.max.saturating_sub(self.current);
+    if remaining == 0 {
+      return Some(Duration::ZERO);
+    }
+    (self.rate > 0.0).then(|| Duration::from_secs_f64(remaining as f64 / self.rate))
+  }
+}
+
+impl Display for Progress {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{}/{} ({:.1}%) {:.1}/s elapsed {} eta {}",
+      self.current,
+      self.max,
+      self.percentage(),
+      self.rate,
+      hms(self.elapsed),
+      self.eta().map(hms).unwrap_or_else(|| String::from("--:--:--"))
+    )?;
+    if self.paused {
+      write!(f, " (paused)")?;
+    }
+    Ok(())
+  }
+}
+
+pub fn hms(duration: Duration) -> String {
+  let secs = duration.as_secs();
+  format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_eta() {
+    let progress = Progress {
+      current: 50,
+      max: 150,
+      rate: 10.0,
+      ..Default::default()
+    };
+    assert_eq!(progress.eta(), Some(Duration::from_secs(10)));
+    assert_eq!(Progress { rate: 0.0, ..progress }.eta(), None);
+    assert_eq!(hms(Duration::from_secs(3725)), "01:02:05");
+  }
+}
@@ -16,6 +16,6 @@ impl KnownArea {
   }
 
   pub fn intersects(&self, other: Position) -> bool {
-    self.inner.get().is_some_and(|area| area.as_position() >= other)
+    self.inner.get().is_some_and(|area| area.contains(other))
   }
 }
@@ -0,0 +1,97 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, process};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+/// Many terminals silently drop larger OSC 52 payloads.
+const MAX_PAYLOAD: usize = 100_000;
+
+/// Where the copied text ended up.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Copied {
+  Terminal,
+  File(PathBuf),
+}
+
+/// OSC 52 through the terminal, so it works over SSH without a clipboard daemon.
+/// Falls back to a temp file when the text is too large or the terminal can't take it.
+pub fn copy(text: &str) -> io::Result<Copied> {
+  let payload = STANDARD.encode(text);
+  if payload.len() <= MAX_PAYLOAD && supports_osc52() && write_osc52(&payload).is_ok() {
+    return Ok(Copied::Terminal);
+  }
+
+  let (path, mut file) = create_unique()?;
+  file.write_all(text.as_bytes())?;
+  Ok(Copied::File(path))
+}
+
+/// A new file only we can read, `/tmp` is shared and may hold someone else's file or symlink under any fixed name.
+fn create_unique() -> io::Result<(PathBuf, File)> {
+  let mut options = OpenOptions::new();
+  options.write(true).create_new(true);
+  #[cfg(unix)]
+  std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+  let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+  let mut attempt = 0;
+  loop {
+    let path = env::temp_dir().join(format!("s3-creator-clipboard-{}-{nanos}-{attempt}.txt", process::id()));
+    match options.open(&path) {
+      Err(err) if err.kind() == ErrorKind::AlreadyExists && attempt < 100 => attempt += 1,
+      opened => return opened.map(|file| (path, file)),
+    }
+  }
+}
+
+/// The Linux console and dumb terminals print the sequence instead of handling it.
+fn supports_osc52() -> bool {
+  !matches!(env::var("TERM").as_deref(), Ok("linux" | "dumb"))
+}
+
+fn write_osc52(payload: &str) -> io::Result<()> {
+  let mut stdout = io::stdout().lock();
+  stdout.write_all(sequence(payload, env::var_os("TMUX").is_some()).as_bytes())?;
+  stdout.flush()
+}
+
+/// tmux only forwards the sequence to the outer terminal when wrapped in its passthrough.
+fn sequence(payload: &str, tmux: bool) -> String {
+  let osc52 = format!("\x1b]52;c;{payload}\x07");
+  if tmux {
+    format!("\x1bPtmux;{}\x1b\\", osc52.replace('\x1b', "\x1b\x1b"))
+  } else {
+    osc52
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_sequence() {
+    let payload = STANDARD.encode("bucket");
+    assert_eq!(sequence(&payload, false), "\x1b]52;c;YnVja2V0\x07");
+    assert_eq!(sequence(&payload, true), "\x1bPtmux;\x1b\x1b]52;c;YnVja2V0\x07\x1b\\");
+  }
+
+  #[test]
+  fn test_create_unique() {
+    let (first, _) = create_unique().unwrap();
+    let (second, _) = create_unique().unwrap();
+    assert_ne!(first, second);
+    #[cfg(unix)]
+    assert_eq!(
+      std::os::unix::fs::PermissionsExt::mode(&first.metadata().unwrap().permissions()) & 0o777,
+      0o600
+    );
+
+    std::fs::remove_file(first).unwrap();
+    std::fs::remove_file(second).unwrap();
+  }
+}
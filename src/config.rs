@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+use std::{env, fs};
+
+use anyhow::{Context as _, bail};
+use ratatui::style::Color;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use toml::{Table, Value};
+
+use crate::app::Settings;
+use crate::app::handler::{AwsRanges, BUCKET_HEAD, Nameservers};
+use crate::widgets::LogConfig;
+
+mod cli;
+pub use cli::*;
+
+mod keymap;
+pub use keymap::*;
+
+static CURRENT: LazyLock<RwLock<Arc<Config>>> = LazyLock::new(Default::default);
+
+/// The configuration in effect, defaults until one is installed.
+pub fn current() -> Arc<Config> {
+  Arc::clone(&CURRENT.read().unwrap())
+}
+
+/// Replaces the configuration read by `current`, for startup and reloads.
+pub fn install(config: Config) {
+  *CURRENT.write().unwrap() = Arc::new(config);
+}
+
+/// Every tunable, layered as defaults, the user file, `--config`, then environment and flags.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+  pub network: Network,
+  pub log: LogConfig,
+  pub channels: Channels,
+  /// Starting values of the "Run settings" popup.
+  pub run: Settings,
+  pub colors: Colors,
+  pub keys: Keymap,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Network {
+  pub resolver: Nameservers,
+  /// AWS published ranges, the region of an address is looked up there first.
+  pub ip_ranges: PathBuf,
+  /// Sent with every anonymous request, a table in the file replaces the console headers.
+  pub headers: BTreeMap<String, String>,
+}
+
+impl Default for Network {
+  fn default() -> Self {
+    Self {
+      resolver: Nameservers::default(),
+      ip_ranges: PathBuf::from("ip-ranges.json"),
+      headers: BUCKET_HEAD
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect(),
+    }
+  }
+}
+
+impl Network {
+  /// Invalid entries are skipped, `Config::validate` reports them.
+  pub fn header_map(&self) -> HeaderMap {
+    self
+      .headers
+      .iter()
+      .filter_map(|(name, value)| Some((HeaderName::from_bytes(name.as_bytes()).ok()?, HeaderValue::from_str(value).ok()?)))
+      .collect()
+  }
+}
+
+/// Queue sizes between the background tasks, only read at startup.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Channels {
+  /// Input lines read ahead of the checks.
+  pub lines: usize,
+  /// Results waiting for the output writer.
+  pub results: usize,
+}
+
+impl Default for Channels {
+  fn default() -> Self {
+    Self { lines: 1, results: 1024 }
+  }
+}
+
+/// Names such as `"cyan"`, indexes such as `"14"` or hex such as `"#00fffb"`.
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Colors {
+  /// Borders of every block.
+  #[serde(deserialize_with = "color")]
+  pub border: Color,
+  /// Border of the focused pane.
+  #[serde(deserialize_with = "color")]
+  pub focus: Color,
+}
+
+impl Default for Colors {
+  fn default() -> Self {
+    Self {
+      border: Color::Rgb(0, 255, 251),
+      focus: Color::Yellow,
+    }
+  }
+}
+
+fn color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+  let value = String::deserialize(deserializer)?;
+  value
+    .parse()
+    .map_err(|_| D::Error::custom(format!("unknown color {value:?}, expected a name, an index or #rrggbb")))
+}
+
+impl Config {
+  /// `$XDG_CONFIG_HOME/s3-creator/config.toml`, `~/.config` when unset.
+  pub fn user_file() -> Option<PathBuf> {
+    let config = env::var_os("XDG_CONFIG_HOME")
+      .filter(|dir| !dir.is_empty())
+      .map(PathBuf::from)
+      .or_else(|| env::home_dir().map(|home| home.join(".config")))?;
+    Some(config.join("s3-creator").join("config.toml"))
+  }
+
+  /// A missing user file is fine, a missing `--config` isn't.
+  pub fn load(cli: &Cli) -> anyhow::Result<Self> {
+    let mut table = Table::new();
+    if let Some(path) = Self::user_file().filter(|path| path.is_file()) {
+      merge(&mut table, Self::read(&path)?);
+    }
+    if let Some(ref path) = cli.config {
+      merge(&mut table, Self::read(path)?);
+    }
+
+    let mut config: Self = Value::Table(table).try_into().context("Invalid configuration")?;
+    cli.apply(&mut config);
+    config.validate()?;
+    Ok(config)
+  }
+
+  fn read(path: &Path) -> anyhow::Result<Table> {
+    let text = fs::read_to_string(path).with_context(|| format!("Can't read {}", path.display()))?;
+    // On its own first, so errors point at a line of this file.
+    toml::from_str::<Self>(&text).with_context(|| format!("Invalid configuration in {}", path.display()))?;
+    Ok(toml::from_str(&text)?)
+  }
+
+  /// What the types can't tell, every problem is listed at once.
+  pub fn validate(&self) -> anyhow::Result<()> {
+    let mut problems = Vec::new();
+    let mut check = |valid: bool, problem: String| {
+      if !valid {
+        problems.push(problem);
+      }
+    };
+
+    let run = &self.run;
+    check(
+      (1..=64).contains(&run.concurrency),
+      format!("run.concurrency must be within 1..=64, got {}", run.concurrency),
+    );
+    check(
+      run.rate_limit <= 1000,
+      format!("run.rate_limit must be within 0..=1000, got {}", run.rate_limit),
+    );
+    let timeout = run.timeout.as_secs();
+    check(
+      (1..=300).contains(&timeout),
+      format!("run.timeout must be within 1..=300 seconds, got {timeout}"),
+    );
+    check(self.log.capacity > 0, String::from("log.capacity must be at least 1"));
+    check(self.log.max_bytes > 0, String::from("log.max_bytes must be at least 1"));
+    check(self.channels.lines > 0, String::from("channels.lines must be at least 1"));
+    check(self.channels.results > 0, String::from("channels.results must be at least 1"));
+    for (name, value) in &self.network.headers {
+      check(
+        HeaderName::from_bytes(name.as_bytes()).is_ok(),
+        format!("network.headers: invalid header name {name:?}"),
+      );
+      check(
+        HeaderValue::from_str(value).is_ok(),
+        format!("network.headers.{name}: invalid value {value:?}"),
+      );
+    }
+    for conflict in self.keys.conflicts() {
+      check(false, conflict);
+    }
+    if let Err(err) = self.network.resolver.resolver() {
+      check(false, format!("network.resolver {:?}: {err}", self.network.resolver.to_string()));
+    }
+    if let Err(err) = AwsRanges::preload(&self.network.ip_ranges) {
+      check(false, format!("network.ip_ranges: {err:#}"));
+    }
+
+    if !problems.is_empty() {
+      bail!("Invalid configuration:\n  {}", problems.join("\n  "));
+    }
+    Ok(())
+  }
+}
+
+/// Tables merge key by key, any other value of `over` replaces the one in `base`.
+fn merge(base: &mut Table, over: Table) {
+  for (key, value) in over {
+    match (base.get_mut(&key), value) {
+      (Some(Value::Table(base)), Value::Table(over)) => merge(base, over),
+      (_, value) => _ = base.insert(key, value),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use clap::Parser;
+
+  use super::*;
+  use crate::app::handler::Provider;
+
+  #[test]
+  fn test_layers() {
+    let mut base = toml::from_str::<Table>("[run]\nconcurrency = 4\ntimeout = 10\n[colors]\nfocus = \"red\"").unwrap();
+    merge(&mut base, toml::from_str("[run]\nconcurrency = 8").unwrap());
+    let mut config: Config = Value::Table(base).try_into().unwrap();
+    assert_eq!((config.run.concurrency, config.run.timeout), (8, Duration::from_secs(10)));
+    assert_eq!(config.colors.focus, Color::Red);
+    assert_eq!(config.network, Network::default());
+
+    let cli = Cli::try_parse_from(["s3-creator", "-j", "16", "--provider", "anonymous", "--log-cap", "10"]).unwrap();
+    cli.apply(&mut config);
+    assert_eq!(config.run.concurrency, 16);
+    assert_eq!(config.run.provider, Provider::Anonymous);
+    assert_eq!(config.log.capacity, 10);
+    assert!(config.validate().is_ok());
+
+    Cli::try_parse_from(["s3-creator", "--public-probe"]).unwrap().apply(&mut config);
+    assert!(config.run.public_probe);
+    Cli::try_parse_from(["s3-creator", "--public-probe=false"]).unwrap().apply(&mut config);
+    assert!(!config.run.public_probe);
+  }
+
+  #[test]
+  fn test_validate() {
+    let typo = toml::from_str::<Config>("[run]\nconcurency = 4").unwrap_err();
+    assert!(typo.to_string().contains("unknown field `concurency`"));
+    assert!(toml::from_str::<Config>("[colors]\nborder = \"mauve\"").is_err());
+
+    let config = toml::from_str::<Config>("[run]\nconcurrency = 0\n[network]\nip_ranges = \"missing.json\"").unwrap();
+    let message = config.validate().unwrap_err().to_string();
+    assert!(message.contains("run.concurrency must be within 1..=64, got 0"));
+    assert!(message.contains("network.ip_ranges: Can't open missing.json"));
+  }
+}
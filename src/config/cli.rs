@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Parser;
+
+use super::Config;
+use crate::app::OutputFormat;
+use crate::app::handler::{Nameservers, Provider, Region};
+
+/// Checks which S3 bucket names are taken.
+///
+/// Flags override their environment variable, which overrides the configuration files.
+#[derive(Debug, Default, Clone, Parser)]
+#[command(version, about)]
+pub struct Cli {
+  /// Read after `$XDG_CONFIG_HOME/s3-creator/config.toml`, its values win.
+  #[arg(short, long, env = "S3_CREATOR_CONFIG", value_name = "FILE")]
+  pub config: Option<PathBuf>,
+  /// DNS servers bucket addresses are resolved with.
+  #[arg(long, env = "S3_CREATOR_RESOLVER")]
+  pub resolver: Option<Nameservers>,
+  /// AWS ip-ranges.json, used to guess the region of a bucket.
+  #[arg(long, env = "S3_CREATOR_IP_RANGES", value_name = "FILE")]
+  pub ip_ranges: Option<PathBuf>,
+  /// Mirror the activity log into this file.
+  #[arg(long, env = "S3_CREATOR_LOG_FILE", value_name = "FILE")]
+  pub log_file: Option<PathBuf>,
+  /// Rotate the log file once it would grow past this size.
+  #[arg(long, env = "S3_CREATOR_LOG_MAX_BYTES", value_name = "BYTES")]
+  pub log_max_bytes: Option<u64>,
+  /// Rotated log files kept.
+  #[arg(long, env = "S3_CREATOR_LOG_FILES", value_name = "COUNT")]
+  pub log_files: Option<usize>,
+  /// Activities kept in memory.
+  #[arg(long, env = "S3_CREATOR_LOG_CAP", value_name = "COUNT")]
+  pub log_cap: Option<usize>,
+  /// Buckets checked at the same time.
+  #[arg(short = 'j', long, env = "S3_CREATOR_CONCURRENCY")]
+  pub concurrency: Option<usize>,
+  /// Checks started per second, 0 for no limit.
+  #[arg(long, env = "S3_CREATOR_RATE_LIMIT")]
+  pub rate_limit: Option<u32>,
+  /// A check taking longer is dropped.
+  #[arg(long, env = "S3_CREATOR_TIMEOUT", value_name = "SECONDS")]
+  pub timeout: Option<u64>,
+  #[arg(long, env = "S3_CREATOR_PROVIDER")]
+  pub provider: Option<Provider>,
+  #[arg(long, env = "S3_CREATOR_FORMAT")]
+  pub format: Option<OutputFormat>,
+  /// Region asked first when the address isn't in a known AWS range.
+  #[arg(long, env = "S3_CREATOR_REGION", value_parser = region)]
+  pub region: Option<Region>,
+  /// Probe the public access of taken buckets, `--public-probe=false` turns it off again.
+  #[arg(long, env = "S3_CREATOR_PUBLIC_PROBE", value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+  pub public_probe: Option<bool>,
+}
+
+fn region(value: &str) -> Result<Region, String> {
+  Region::from_name(value).ok_or_else(|| format!("unknown region {value:?}"))
+}
+
+impl Cli {
+  /// Overrides what was given, the rest of `config` is left as is.
+  pub fn apply(&self, config: &mut Config) {
+    if let Some(resolver) = self.resolver {
+      config.network.resolver = resolver;
+    }
+    if let Some(ref ip_ranges) = self.ip_ranges {
+      config.network.ip_ranges = ip_ranges.clone();
+    }
+    if let Some(ref file) = self.log_file {
+      config.log.file = Some(file.clone());
+    }
+    if let Some(max_bytes) = self.log_max_bytes {
+      config.log.max_bytes = max_bytes;
+    }
+    if let Some(max_files) = self.log_files {
+      config.log.max_files = max_files;
+    }
+    if let Some(capacity) = self.log_cap {
+      config.log.capacity = capacity;
+    }
+    if let Some(concurrency) = self.concurrency {
+      config.run.concurrency = concurrency;
+    }
+    if let Some(rate_limit) = self.rate_limit {
+      config.run.rate_limit = rate_limit;
+    }
+    if let Some(timeout) = self.timeout {
+      config.run.timeout = Duration::from_secs(timeout);
+    }
+    if let Some(provider) = self.provider {
+      config.run.provider = provider;
+    }
+    if let Some(format) = self.format {
+      config.run.output_format = format;
+    }
+    if let Some(region) = self.region {
+      config.run.default_region = region;
+    }
+    if let Some(public_probe) = self.public_probe {
+      config.run.public_probe = public_probe;
+    }
+  }
+}
@@ -0,0 +1,489 @@
+use std::collections::BTreeMap;
+
+use crossterm::event::KeyEvent;
+use serde::Deserialize;
+use strum::{Display, EnumMessage, EnumString, VariantArray};
+use toml::Value;
+
+mod combo;
+pub use combo::*;
+
+/// Where a binding applies, the `[keys.<context>]` tables of the configuration file.
+/// The file picker, forms, confirmations and details popups aren't one, they keep the keys shown in their footers.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Display, VariantArray, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Context {
+  /// Always active, also under popups that leave the key alone.
+  Global,
+  /// The focused bottom pane, whichever it is.
+  Panes,
+  Logs,
+  Results,
+  /// Text prompts, alone since typing must not trigger anything else.
+  Input,
+}
+
+impl Context {
+  pub fn title(self) -> &'static str {
+    match self {
+      Self::Global => "Everywhere",
+      Self::Panes => "Both panes",
+      Self::Logs => "Activities pane",
+      Self::Results => "Results pane",
+      Self::Input => "Text prompts",
+    }
+  }
+
+  /// Contexts tried together, the first one wins.
+  pub fn layers(self) -> &'static [Context] {
+    match self {
+      Self::Global => &[Self::Global],
+      Self::Panes => &[Self::Panes, Self::Global],
+      Self::Logs => &[Self::Logs, Self::Panes, Self::Global],
+      Self::Results => &[Self::Results, Self::Panes, Self::Global],
+      Self::Input => &[Self::Input],
+    }
+  }
+}
+
+/// Everything a key can be bound to, named in snake case in the configuration file.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Display, EnumString, EnumMessage, VariantArray)]
+#[strum(serialize_all = "snake_case")]
+pub enum Action {
+  #[strum(message = "Choose the input file")]
+  ChooseInput,
+  #[strum(message = "Choose the output file")]
+  ChooseOutput,
+  #[strum(message = "Run settings")]
+  RunSettings,
+  #[strum(message = "Reload the configuration")]
+  ReloadConfig,
+  #[strum(message = "Start processing")]
+  Start,
+  #[strum(message = "Pause processing")]
+  Pause,
+  #[strum(message = "Toggle public access probing")]
+  TogglePublicProbe,
+  #[strum(message = "Quit")]
+  Quit,
+  #[strum(message = "Show this help")]
+  Help,
+  #[strum(message = "Switch pane")]
+  SwitchPane,
+  #[strum(message = "Export the shown entries")]
+  Export,
+  #[strum(message = "Copy the selected entry")]
+  CopySelected,
+  #[strum(message = "Copy every available name")]
+  CopyNames,
+  #[strum(message = "Details of the selected entry")]
+  Details,
+  #[strum(message = "Search")]
+  Search,
+  #[strum(message = "Next match")]
+  NextMatch,
+  #[strum(message = "Previous match")]
+  PreviousMatch,
+  #[strum(message = "Scroll up")]
+  ScrollUp,
+  #[strum(message = "Scroll down")]
+  ScrollDown,
+  #[strum(message = "Page up")]
+  PageUp,
+  #[strum(message = "Page down")]
+  PageDown,
+  #[strum(message = "Go to the top")]
+  Top,
+  #[strum(message = "Go to the bottom")]
+  Bottom,
+  #[strum(message = "Submit")]
+  Submit,
+  #[strum(message = "Cancel")]
+  Cancel,
+  #[strum(message = "Next completion")]
+  Complete,
+  #[strum(message = "Previous completion")]
+  CompletePrevious,
+  #[strum(message = "Older history entry")]
+  HistoryPrevious,
+  #[strum(message = "Newer history entry")]
+  HistoryNext,
+  #[strum(message = "Delete the previous character")]
+  DeleteBackward,
+  #[strum(message = "Delete the next character")]
+  DeleteForward,
+  #[strum(message = "Cut the previous word")]
+  KillWordBackward,
+  #[strum(message = "Cut the next word")]
+  KillWordForward,
+  #[strum(message = "Cut to the start")]
+  KillToStart,
+  #[strum(message = "Cut to the end")]
+  KillToEnd,
+  #[strum(message = "Paste the last cut")]
+  Yank,
+  #[strum(message = "Cycle through older cuts")]
+  YankPop,
+  #[strum(message = "Undo")]
+  Undo,
+  #[strum(message = "Redo")]
+  Redo,
+  #[strum(message = "Move left")]
+  Left,
+  #[strum(message = "Move right, accepts the suggestion at the end")]
+  Right,
+  #[strum(message = "Move a word left")]
+  WordLeft,
+  #[strum(message = "Move a word right")]
+  WordRight,
+  #[strum(message = "Move to the start")]
+  Home,
+  #[strum(message = "Move to the end, accepts the suggestion")]
+  End,
+  #[strum(message = "Select left")]
+  SelectLeft,
+  #[strum(message = "Select right")]
+  SelectRight,
+  #[strum(message = "Select a word left")]
+  SelectWordLeft,
+  #[strum(message = "Select a word right")]
+  SelectWordRight,
+  #[strum(message = "Select to the start")]
+  SelectHome,
+  #[strum(message = "Select to the end")]
+  SelectEnd,
+}
+
+/// Bindings shipped with the application, in the order they are listed.
+const DEFAULTS: &[(Context, Action, &[&str])] = &[
+  (Context::Global, Action::ChooseInput, &["i"]),
+  (Context::Global, Action::ChooseOutput, &["o"]),
+  (Context::Global, Action::Start, &["s"]),
+  (Context::Global, Action::Pause, &["esc"]),
+  (Context::Global, Action::RunSettings, &["r"]),
+  (Context::Global, Action::TogglePublicProbe, &["p"]),
+  (Context::Global, Action::ReloadConfig, &["R"]),
+  (Context::Global, Action::Quit, &["ctrl+c"]),
+  (Context::Global, Action::Help, &["?"]),
+  (Context::Panes, Action::SwitchPane, &["tab"]),
+  (Context::Panes, Action::Details, &["enter"]),
+  (Context::Panes, Action::CopySelected, &["y"]),
+  (Context::Panes, Action::CopyNames, &["Y"]),
+  (Context::Panes, Action::Export, &["x"]),
+  (Context::Logs, Action::Search, &["/"]),
+  (Context::Logs, Action::NextMatch, &["n"]),
+  (Context::Logs, Action::PreviousMatch, &["N"]),
+  (Context::Logs, Action::ScrollUp, &["up"]),
+  (Context::Logs, Action::ScrollDown, &["down"]),
+  (Context::Logs, Action::PageUp, &["pageup"]),
+  (Context::Logs, Action::PageDown, &["pagedown"]),
+  (Context::Logs, Action::Top, &["home"]),
+  (Context::Logs, Action::Bottom, &["end"]),
+  (Context::Results, Action::ScrollUp, &["up"]),
+  (Context::Results, Action::ScrollDown, &["down"]),
+  (Context::Results, Action::PageUp, &["pageup"]),
+  (Context::Results, Action::PageDown, &["pagedown"]),
+  (Context::Results, Action::Top, &["home"]),
+  (Context::Results, Action::Bottom, &["end"]),
+  (Context::Input, Action::Submit, &["enter"]),
+  (Context::Input, Action::Cancel, &["esc"]),
+  (Context::Input, Action::Complete, &["tab"]),
+  (Context::Input, Action::CompletePrevious, &["backtab"]),
+  (Context::Input, Action::HistoryPrevious, &["up"]),
+  (Context::Input, Action::HistoryNext, &["down"]),
+  (Context::Input, Action::DeleteBackward, &["backspace", "ctrl+h"]),
+  (Context::Input, Action::DeleteForward, &["delete", "ctrl+d"]),
+  (Context::Input, Action::KillWordBackward, &["ctrl+w", "alt+backspace"]),
+  (Context::Input, Action::KillWordForward, &["alt+d"]),
+  (Context::Input, Action::KillToStart, &["ctrl+u"]),
+  (Context::Input, Action::KillToEnd, &["ctrl+k"]),
+  (Context::Input, Action::Yank, &["ctrl+y"]),
+  (Context::Input, Action::YankPop, &["alt+y"]),
+  (Context::Input, Action::Undo, &["ctrl+z"]),
+  (Context::Input, Action::Redo, &["alt+z", "ctrl+shift+z"]),
+  (Context::Input, Action::Left, &["left", "ctrl+b"]),
+  (Context::Input, Action::Right, &["right", "ctrl+f"]),
+  (Context::Input, Action::WordLeft, &["ctrl+left", "alt+left", "alt+b"]),
+  (Context::Input, Action::WordRight, &["ctrl+right", "alt+right", "alt+f"]),
+  (Context::Input, Action::Home, &["home", "ctrl+a"]),
+  (Context::Input, Action::End, &["end", "ctrl+e"]),
+  (Context::Input, Action::SelectLeft, &["shift+left"]),
+  (Context::Input, Action::SelectRight, &["shift+right"]),
+  (Context::Input, Action::SelectWordLeft, &["ctrl+shift+left"]),
+  (Context::Input, Action::SelectWordRight, &["ctrl+shift+right"]),
+  (Context::Input, Action::SelectHome, &["shift+home"]),
+  (Context::Input, Action::SelectEnd, &["shift+end"]),
+];
+
+impl Action {
+  pub fn describe(self) -> &'static str {
+    self.get_message().unwrap_or_default()
+  }
+
+  /// Shorter name for the hint bar.
+  pub fn hint(self) -> &'static str {
+    match self {
+      Self::Start => "Start",
+      Self::Pause => "Pause",
+      Self::ChooseInput => "Input",
+      Self::ChooseOutput => "Output",
+      Self::RunSettings => "Settings",
+      Self::SwitchPane => "Switch",
+      Self::CopySelected => "Copy",
+      Self::Details => "Details",
+      Self::Help => "Help",
+      action => action.describe(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Entry {
+  pub context: Context,
+  pub action: Action,
+  pub bindings: Vec<Binding>,
+}
+
+/// What the last key did to a `Chord`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Dispatch {
+  Action(Action),
+  /// Part of a longer binding, waiting for the next key.
+  Pending,
+  Unbound,
+}
+
+/// Keys typed so far of a multi-key binding.
+#[derive(Debug, Clone, Default)]
+pub struct Chord(Vec<KeyCombo>);
+
+/// Actions of every context with their bindings, the defaults overridden by `[keys]`.
+/// An action listed in the file replaces all of its default bindings, `[]` unbinds it.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "BTreeMap<Context, BTreeMap<String, Value>>")]
+pub struct Keymap {
+  entries: Vec<Entry>,
+}
+
+impl Default for Keymap {
+  fn default() -> Self {
+    let entries = DEFAULTS
+      .iter()
+      .map(|(context, action, bindings)| Entry {
+        context: *context,
+        action: *action,
+        bindings: bindings
+          .iter()
+          .map(|binding| binding.parse().expect("default bindings are valid"))
+          .collect(),
+      })
+      .collect();
+    Self { entries }
+  }
+}
+
+impl TryFrom<BTreeMap<Context, BTreeMap<String, Value>>> for Keymap {
+  type Error = String;
+
+  fn try_from(overrides: BTreeMap<Context, BTreeMap<String, Value>>) -> Result<Self, Self::Error> {
+    let mut keymap = Self::default();
+    for (context, actions) in overrides {
+      for (name, value) in actions {
+        let path = format!("keys.{context}.{name}");
+        let entry = name
+          .parse::<Action>()
+          .ok()
+          .and_then(|action| keymap.entries.iter_mut().find(|entry| entry.context == context && entry.action == action))
+          .ok_or_else(|| format!("{path}: no such action in the {context} context"))?;
+        let bindings = match value {
+          Value::String(binding) => vec![binding],
+          Value::Array(bindings) => bindings
+            .into_iter()
+            .map(|binding| match binding {
+              Value::String(binding) => Ok(binding),
+              other => Err(format!("{path}: expected a key such as \"ctrl+s\", got {other}")),
+            })
+            .collect::<Result<_, _>>()?,
+          other => return Err(format!("{path}: expected a key such as \"ctrl+s\" or a list of them, got {other}")),
+        };
+        entry.bindings = bindings
+          .iter()
+          .map(|binding| binding.parse().map_err(|err| format!("{path}: {err}")))
+          .collect::<Result<_, _>>()?;
+      }
+    }
+    Ok(keymap)
+  }
+}
+
+impl Keymap {
+  /// Every action of `context` in listing order, unbound ones included.
+  pub fn entries(&self, context: Context) -> impl Iterator<Item = &Entry> {
+    self.entries.iter().filter(move |entry| entry.context == context)
+  }
+
+  /// Bindings of `action` in the first of `context`'s layers that has it.
+  pub fn bindings(&self, context: Context, action: Action) -> &[Binding] {
+    context
+      .layers()
+      .iter()
+      .find_map(|layer| self.entries(*layer).find(|entry| entry.action == action))
+      .map_or(&[], |entry| &entry.bindings)
+  }
+
+  /// Bindings reachable together that are equal or where one is the start of the other,
+  /// and prompt bindings starting with a character that could then no longer be typed.
+  pub fn conflicts(&self) -> Vec<String> {
+    let mut conflicts = Vec::new();
+    for entry in self.entries(Context::Input) {
+      for binding in entry.bindings.iter().filter(|binding| binding.keys()[0].is_printable()) {
+        let typed = binding.keys()[0];
+        conflicts.push(format!(
+          "\"{binding}\" of keys.input.{} leaves no way to type \"{typed}\" in prompts",
+          entry.action
+        ));
+      }
+    }
+    for context in Context::VARIANTS {
+      let layers = context.layers();
+      let bound = self
+        .entries
+        .iter()
+        .filter(|entry| layers.contains(&entry.context))
+        .flat_map(|entry| entry.bindings.iter().map(move |binding| (entry, binding)))
+        .collect::<Vec<_>>();
+
+      for (index, (first, first_binding)) in bound.iter().enumerate() {
+        for (second, second_binding) in &bound[index + 1..] {
+          if first.context == second.context && first.action == second.action {
+            continue;
+          }
+          // Only report pairs once, from the context they both belong to first.
+          if ![first.context, second.context].contains(context) {
+            continue;
+          }
+          let name = |entry: &Entry| format!("keys.{}.{}", entry.context, entry.action);
+          let conflict = if first_binding == second_binding {
+            format!("\"{first_binding}\" is bound to both {} and {}", name(first), name(second))
+          } else if first_binding.shadows(second_binding) {
+            format!("\"{first_binding}\" of {} hides \"{second_binding}\" of {}", name(first), name(second))
+          } else if second_binding.shadows(first_binding) {
+            format!("\"{second_binding}\" of {} hides \"{first_binding}\" of {}", name(second), name(first))
+          } else {
+            continue;
+          };
+          if !conflicts.contains(&conflict) {
+            conflicts.push(conflict);
+          }
+        }
+      }
+    }
+    conflicts
+  }
+
+  /// Follows `chord` with `key` through the layers of `context`.
+  /// A key that ends a chord without completing it is tried again on its own.
+  pub fn dispatch(&self, context: Context, chord: &mut Chord, key: KeyEvent) -> Dispatch {
+    let Some(combo) = KeyCombo::from_event(key) else {
+      return Dispatch::Unbound;
+    };
+
+    chord.0.push(combo);
+    loop {
+      let mut pending = false;
+      for layer in context.layers() {
+        for entry in self.entries(*layer) {
+          for binding in &entry.bindings {
+            if binding.keys() == chord.0.as_slice() {
+              chord.0.clear();
+              return Dispatch::Action(entry.action);
+            }
+            pending |= binding.keys().starts_with(&chord.0);
+          }
+        }
+      }
+
+      if pending {
+        return Dispatch::Pending;
+      }
+      if chord.0.len() == 1 {
+        chord.0.clear();
+        return Dispatch::Unbound;
+      }
+      chord.0 = vec![combo];
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crossterm::event::{KeyCode, KeyModifiers};
+
+  use super::*;
+
+  fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+  }
+
+  fn overridden(overrides: &str) -> Result<Keymap, String> {
+    Keymap::try_from(toml::from_str::<BTreeMap<Context, BTreeMap<String, Value>>>(overrides).unwrap())
+  }
+
+  #[test]
+  fn test_defaults() {
+    let keymap = Keymap::default();
+    assert_eq!(keymap.conflicts(), Vec::<String>::new());
+    for action in Action::VARIANTS {
+      assert!(keymap.entries.iter().any(|entry| entry.action == *action), "{action} has no context");
+    }
+  }
+
+  #[test]
+  fn test_overrides() {
+    let keymap = overridden("[logs]\ntop = \"g g\"\nbottom = [\"G\", \"end\"]\n[global]\nstart = \"g\"").unwrap();
+    assert_eq!(keymap.bindings(Context::Logs, Action::Top), ["g g".parse().unwrap()]);
+    assert_eq!(keymap.bindings(Context::Results, Action::Start), ["g".parse().unwrap()]);
+    assert_eq!(keymap.conflicts(), ["\"g\" of keys.global.start hides \"g g\" of keys.logs.top"]);
+
+    assert_eq!(
+      overridden("[logs]\nstart = \"s\"").unwrap_err(),
+      "keys.logs.start: no such action in the logs context"
+    );
+    assert!(overridden("[input]\nundo = [\"ctrl+zz\"]").unwrap_err().contains("unknown key \"zz\""));
+
+    let keymap = overridden("[input]\nundo = [\"u\", \"ctrl+z\"]\nredo = \"R\"").unwrap();
+    let conflicts = keymap.conflicts();
+    assert_eq!(conflicts.len(), 2);
+    assert_eq!(conflicts[0], "\"u\" of keys.input.undo leaves no way to type \"u\" in prompts");
+    assert!(conflicts[1].starts_with("\"R\" of keys.input.redo"));
+  }
+
+  #[test]
+  fn test_dispatch() {
+    let keymap = overridden("[logs]\ntop = \"g g\"").unwrap();
+    let mut chord = Chord::default();
+
+    assert_eq!(keymap.dispatch(Context::Logs, &mut chord, key(KeyCode::Char('g'))), Dispatch::Pending);
+    assert_eq!(
+      keymap.dispatch(Context::Logs, &mut chord, key(KeyCode::Char('g'))),
+      Dispatch::Action(Action::Top)
+    );
+    assert_eq!(keymap.dispatch(Context::Logs, &mut chord, key(KeyCode::Char('g'))), Dispatch::Pending);
+    assert_eq!(
+      keymap.dispatch(Context::Logs, &mut chord, key(KeyCode::Char('s'))),
+      Dispatch::Action(Action::Start)
+    );
+    assert_eq!(keymap.dispatch(Context::Results, &mut chord, key(KeyCode::Char('g'))), Dispatch::Unbound);
+    assert_eq!(
+      keymap.dispatch(Context::Input, &mut chord, key(KeyCode::Esc)),
+      Dispatch::Action(Action::Cancel)
+    );
+
+    let shifted = KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT);
+    assert_eq!(
+      keymap.dispatch(Context::Logs, &mut chord, shifted),
+      Dispatch::Action(Action::PreviousMatch)
+    );
+  }
+}
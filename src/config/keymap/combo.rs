@@ -0,0 +1,208 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+/// One key with its modifiers, written as `ctrl+s`, `alt+left`, `Y` or `?`.
+///
+/// Shift is folded into characters, `shift+y` and `Y` are the same key whatever the terminal reports.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct KeyCombo {
+  code: KeyCode,
+  modifiers: KeyModifiers,
+}
+
+impl KeyCombo {
+  pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+    let (code, modifiers) = match code {
+      KeyCode::Char(chr) if modifiers.contains(KeyModifiers::SHIFT) => (KeyCode::Char(chr.to_ascii_uppercase()), modifiers - KeyModifiers::SHIFT),
+      KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => (KeyCode::BackTab, modifiers - KeyModifiers::SHIFT),
+      KeyCode::BackTab => (KeyCode::BackTab, modifiers - KeyModifiers::SHIFT),
+      code => (code, modifiers),
+    };
+    Self { code, modifiers }
+  }
+
+  /// Like `Display` with names and modifiers in capitals, as shown in the controls.
+  pub fn label(&self) -> String {
+    match self.code {
+      KeyCode::Char(chr) if chr != ' ' => {
+        let label = self.to_string();
+        let (modifiers, key) = label.split_at(label.len() - chr.len_utf8());
+        modifiers.to_uppercase() + key
+      }
+      _ => self.to_string().to_uppercase(),
+    }
+  }
+
+  /// A character typed as is, shift included since it is folded in.
+  pub fn is_printable(&self) -> bool {
+    matches!(self.code, KeyCode::Char(_)) && self.modifiers.is_empty()
+  }
+
+  /// Only presses are bound, releases and repeats give `None`.
+  pub fn from_event(key: KeyEvent) -> Option<Self> {
+    (key.kind == KeyEventKind::Press).then(|| Self::new(key.code, key.modifiers))
+  }
+}
+
+const MODIFIERS: [(&str, KeyModifiers); 4] = [
+  ("ctrl", KeyModifiers::CONTROL),
+  ("alt", KeyModifiers::ALT),
+  ("shift", KeyModifiers::SHIFT),
+  ("super", KeyModifiers::SUPER),
+];
+
+const NAMES: [(&str, KeyCode); 15] = [
+  ("esc", KeyCode::Esc),
+  ("enter", KeyCode::Enter),
+  ("tab", KeyCode::Tab),
+  ("backtab", KeyCode::BackTab),
+  ("backspace", KeyCode::Backspace),
+  ("delete", KeyCode::Delete),
+  ("insert", KeyCode::Insert),
+  ("home", KeyCode::Home),
+  ("end", KeyCode::End),
+  ("pageup", KeyCode::PageUp),
+  ("pagedown", KeyCode::PageDown),
+  ("up", KeyCode::Up),
+  ("down", KeyCode::Down),
+  ("left", KeyCode::Left),
+  ("right", KeyCode::Right),
+];
+
+fn named(name: &str) -> Option<KeyCode> {
+  let name = name.to_ascii_lowercase();
+  let code = match name.as_str() {
+    "space" => KeyCode::Char(' '),
+    "escape" => KeyCode::Esc,
+    "return" => KeyCode::Enter,
+    "del" => KeyCode::Delete,
+    "pgup" => KeyCode::PageUp,
+    "pgdn" => KeyCode::PageDown,
+    name => match name.strip_prefix('f').and_then(|number| number.parse().ok()) {
+      Some(number @ 1..=24) => KeyCode::F(number),
+      _ => NAMES.iter().find(|(known, _)| *known == name)?.1,
+    },
+  };
+  Some(code)
+}
+
+impl FromStr for KeyCombo {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    // The `+` key itself is written `+` or `ctrl++`.
+    let (modifiers, key) = match value {
+      "+" => ("", "+"),
+      _ if value.ends_with("++") => (&value[..value.len() - 2], "+"),
+      _ => value.rsplit_once('+').unwrap_or(("", value)),
+    };
+
+    let mut parsed = KeyModifiers::NONE;
+    for modifier in modifiers.split('+').filter(|modifier| !modifier.is_empty()) {
+      let name = modifier.to_ascii_lowercase().replace("control", "ctrl");
+      let Some((_, known)) = MODIFIERS.iter().find(|(known, _)| *known == name) else {
+        return Err(format!("unknown modifier {modifier:?} in {value:?}"));
+      };
+      parsed |= *known;
+    }
+
+    let mut chars = key.chars();
+    let code = match (chars.next(), chars.next()) {
+      (Some(chr), None) => KeyCode::Char(chr),
+      _ => named(key).ok_or_else(|| format!("unknown key {key:?} in {value:?}"))?,
+    };
+    Ok(Self::new(code, parsed))
+  }
+}
+
+impl Display for KeyCombo {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    for (name, modifier) in MODIFIERS {
+      if self.modifiers.contains(modifier) {
+        write!(f, "{name}+")?;
+      }
+    }
+    match self.code {
+      KeyCode::Char(' ') => write!(f, "space"),
+      KeyCode::Char(chr) => write!(f, "{chr}"),
+      KeyCode::F(number) => write!(f, "f{number}"),
+      code => match NAMES.iter().find(|(_, known)| *known == code) {
+        Some((name, _)) => write!(f, "{name}"),
+        None => write!(f, "{code:?}"),
+      },
+    }
+  }
+}
+
+/// Keys pressed one after the other, written space separated such as `g g`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Binding(Vec<KeyCombo>);
+
+impl Binding {
+  pub fn keys(&self) -> &[KeyCombo] {
+    &self.0
+  }
+
+  pub fn label(&self) -> String {
+    self.0.iter().map(KeyCombo::label).collect::<Vec<_>>().join(" ")
+  }
+
+  /// `self` has to be completed before `other` can be reached, or they are the same.
+  pub fn shadows(&self, other: &Binding) -> bool {
+    other.0.starts_with(&self.0)
+  }
+}
+
+impl FromStr for Binding {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let keys = value.split_whitespace().map(KeyCombo::from_str).collect::<Result<Vec<_>, _>>()?;
+    if keys.is_empty() {
+      return Err(String::from("empty key binding"));
+    }
+    Ok(Self(keys))
+  }
+}
+
+impl Display for Binding {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    for (index, key) in self.0.iter().enumerate() {
+      if index > 0 {
+        write!(f, " ")?;
+      }
+      write!(f, "{key}")?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let ctrl_s = KeyCombo::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+    assert_eq!("ctrl+s".parse(), Ok(ctrl_s));
+    assert_eq!("Control+s".parse(), Ok(ctrl_s));
+    assert_eq!("shift+y".parse(), Ok(KeyCombo::new(KeyCode::Char('Y'), KeyModifiers::NONE)));
+    assert_eq!("shift+tab".parse(), Ok(KeyCombo::new(KeyCode::BackTab, KeyModifiers::NONE)));
+    assert_eq!("ctrl++".parse(), Ok(KeyCombo::new(KeyCode::Char('+'), KeyModifiers::CONTROL)));
+    assert_eq!("F5".parse(), Ok(KeyCombo::new(KeyCode::F(5), KeyModifiers::NONE)));
+    assert!("hyper+s".parse::<KeyCombo>().is_err());
+    assert!("enterr".parse::<KeyCombo>().is_err());
+
+    let event = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL | KeyModifiers::SHIFT);
+    assert_eq!(KeyCombo::from_event(event), "ctrl+shift+z".parse().ok());
+
+    let chord: Binding = "g  g".parse().unwrap();
+    assert_eq!(chord.to_string(), "g g");
+    assert!("g".parse::<Binding>().unwrap().shadows(&chord));
+    assert_eq!("alt+shift+left".parse::<Binding>().unwrap().to_string(), "alt+shift+left");
+    assert_eq!("ctrl+y g g".parse::<Binding>().unwrap().label(), "CTRL+y g g");
+    assert_eq!("esc".parse::<Binding>().unwrap().label(), "ESC");
+  }
+}
@@ -1,5 +1,7 @@
 use std::io::{Result, stdout};
+use std::process::ExitCode;
 
+use clap::Parser;
 use crossterm::event::{DisableMouseCapture, EnableMouseCapture, Event, MouseEvent};
 use crossterm::execute;
 use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode};
@@ -11,15 +13,28 @@ use tokio::select;
 use tokio::task::block_in_place;
 
 use crate::app::App;
+use crate::config::{Cli, Config};
 
 pub type Area = Rect;
 pub mod app;
 pub mod areas;
+pub mod clipboard;
+pub mod config;
 pub mod ui;
 pub mod widgets;
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> Result<ExitCode> {
+  // Checked before the terminal is taken over, so the errors stay readable.
+  let cli = Cli::parse();
+  let config = match Config::load(&cli) {
+    Ok(config) => config,
+    Err(err) => {
+      eprintln!("s3-creator: {err:#}");
+      return Ok(ExitCode::FAILURE);
+    }
+  };
+
   let backend = CrosstermBackend::new(stdout());
   let mut terminal = Terminal::new(backend)?;
   terminal.hide_cursor()?;
@@ -28,7 +43,7 @@ async fn main() -> Result<()> {
 
   let mut status: Result<()> = Ok(());
   let mut event = PollEvent::default();
-  let mut app = App::default();
+  let mut app = App::new(cli, config);
   let mut app_event = app.subscribe_event();
 
   // Initiate first render
@@ -47,6 +62,7 @@ async fn main() -> Result<()> {
     };
 
     if handled.kind.is_render() {
+      app.tick();
       terminal.draw(|frame| frame.render_widget(&app, frame.area()))?;
     } else if handled.kind.is_handled() {
       break;
@@ -57,7 +73,7 @@ async fn main() -> Result<()> {
   execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
   terminal.show_cursor()?;
 
-  status
+  status.map(|_| ExitCode::SUCCESS)
 }
 
 #[macro_export]
@@ -1,8 +1,26 @@
+use std::cell::Cell;
+
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Rect};
-use ratatui::style::{Color, Stylize};
+use ratatui::style::Stylize;
 use ratatui::widgets::{Block, BorderType, Clear, Widget};
 
+use crate::config::Colors;
+
+thread_local! {
+  /// Colors of the frame being drawn, see `paint_with`.
+  static COLORS: Cell<Colors> = Cell::new(Colors::default());
+}
+
+/// Takes the configured colors once per frame instead of on every block.
+pub fn paint_with(colors: Colors) {
+  COLORS.set(colors);
+}
+
+pub fn colors() -> Colors {
+  COLORS.get()
+}
+
 pub fn fix_center(area: Rect, w: u16, h: u16) -> Rect {
   let [_, x, _] = Layout::horizontal([Constraint::Fill(1), Constraint::Length(w), Constraint::Fill(1)]).areas(area);
   Layout::vertical([Constraint::Fill(1), Constraint::Length(h), Constraint::Fill(1)]).split(x)[1]
@@ -14,5 +32,5 @@ pub fn clear(area: Rect, buffer: &mut Buffer) {
 
 #[inline(always)]
 pub fn blk<'a>() -> Block<'a> {
-  Block::bordered().border_type(BorderType::Rounded).fg(Color::Rgb(0, 255, 251))
+  Block::bordered().border_type(BorderType::Rounded).fg(colors().border)
 }
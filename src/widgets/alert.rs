@@ -1,5 +1,6 @@
 use std::ops::Deref;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use crossterm::event::Event;
 use helper::{UnhandledEvent, keys};
@@ -12,10 +13,14 @@ use ratatui::widgets::{Paragraph, Wrap};
 
 use crate::areas::KnownArea;
 use crate::ui::{blk, clear};
+use crate::widgets::Severity;
 
 pub struct Alert {
   title: Rc<str>,
   content: Vec<Rc<str>>,
+  severity: Severity,
+  /// Dismissed on its own past this point.
+  expires: Option<Instant>,
   known_area: KnownArea,
 }
 
@@ -24,10 +29,26 @@ impl Alert {
     Self {
       title: title.into(),
       content: Vec::from_iter(content.map(Into::into)),
+      severity: Severity::default(),
+      expires: None,
       known_area: Default::default(),
     }
   }
 
+  pub fn severity(mut self, severity: Severity) -> Self {
+    self.severity = severity;
+    self
+  }
+
+  pub fn dismiss_after(mut self, timeout: Duration) -> Self {
+    self.expires = Some(Instant::now() + timeout);
+    self
+  }
+
+  pub fn is_expired(&self) -> bool {
+    self.expires.is_some_and(|expires| expires <= Instant::now())
+  }
+
   pub fn add_line(mut self, line: impl Into<Rc<str>>) -> Self {
     self.content.push(line.into());
     self
@@ -35,12 +56,12 @@ impl Alert {
 
   pub fn handle_event(&mut self, event: &Event) -> Option<UnhandledEvent> {
     match event {
-      Event::Key(keys!(Char('y'), NONE, Press) | keys!(Enter, NONE, Press)) => Some(UnhandledEvent::handled()),
+      Event::Key(keys!(Char('y'), NONE, Press) | keys!(Enter, NONE, Press) | keys!(Esc, NONE, Press)) => Some(UnhandledEvent::handled()),
       _ => None,
     }
   }
 
-  fn as_lines(&self) -> impl Iterator<Item = Line> {
+  fn as_lines(&self) -> impl Iterator<Item = Line<'_>> {
     self
       .content
       .iter()
@@ -50,8 +71,12 @@ impl Alert {
       .map(Line::from)
   }
 
-  fn line_controls(&self) -> Line {
-    let spans = vec![Span::raw("[Y/ENTER]").blue(), Span::raw(" "), Span::raw("OK")];
+  fn line_controls(&self) -> Line<'_> {
+    let mut spans = vec![Span::raw("[Y/ENTER]").blue(), Span::raw(" "), Span::raw("OK")];
+    if let Some(expires) = self.expires {
+      let left = expires.saturating_duration_since(Instant::now()).as_secs() + 1;
+      spans.push(Span::raw(format!(" ({left}s)")).fg(Color::DarkGray));
+    }
     Line::from(spans).centered()
   }
 }
@@ -64,10 +89,8 @@ impl Widget for &Alert {
     clear(area, buf);
     self.known_area.replace(area);
 
-    let block = blk()
-      .title_top(String::from_utf8_lossy(self.title.as_bytes()))
-      .title_bottom(self.line_controls())
-      .fg(Color::Rgb(255, 123, 0));
+    let title = format!("{} {}", self.severity.icon(), self.title);
+    let block = blk().title_top(title).title_bottom(self.line_controls()).fg(self.severity.color());
 
     Paragraph::new(Text::from_iter(self.as_lines()))
       .block(block)
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::prelude::{Color, Stylize, Widget};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Bar, BarChart, BarGroup, Paragraph};
+use strum::VariantArray;
+
+use crate::app::handler::{BucketStatus, Region, Status};
+use crate::ui::blk;
+
+/// Lock-free result counters, cloned into every checker task.
+#[derive(Debug, Clone)]
+pub struct Breakdown {
+  available: Arc<AtomicUsize>,
+  unavailable: Arc<AtomicUsize>,
+  unknown: Arc<AtomicUsize>,
+  errors: Arc<AtomicUsize>,
+  /// Indexed like `Region::VARIANTS`.
+  regions: Arc<[AtomicUsize]>,
+}
+
+impl Default for Breakdown {
+  fn default() -> Self {
+    Self {
+      available: Default::default(),
+      unavailable: Default::default(),
+      unknown: Default::default(),
+      errors: Default::default(),
+      regions: Region::VARIANTS.iter().map(|_| AtomicUsize::new(0)).collect(),
+    }
+  }
+}
+
+impl Breakdown {
+  pub fn record(&self, bucket: &BucketStatus) {
+    let counter = match bucket.status() {
+      Status::Available => &self.available,
+      Status::Exists | Status::ExistsPublic => &self.unavailable,
+      Status::Throttled | Status::DnsFailure | Status::Timeout | Status::TlsError => &self.errors,
+      Status::WrongRegion | Status::InvalidName | Status::Unknown(_) => &self.unknown,
+    };
+    counter.fetch_add(1, Ordering::Relaxed);
+
+    if !bucket.status().is_error()
+      && let Some(index) = Region::VARIANTS.iter().position(|region| *region == bucket.region())
+    {
+      self.regions[index].fetch_add(1, Ordering::Relaxed);
+    }
+  }
+
+  /// A check that failed before producing any `BucketStatus`.
+  pub fn record_error(&self) {
+    self.errors.fetch_add(1, Ordering::Relaxed);
+  }
+
+  pub fn reset(&self) {
+    for counter in [&self.available, &self.unavailable, &self.unknown, &self.errors] {
+      counter.store(0, Ordering::Relaxed);
+    }
+    for counter in self.regions.iter() {
+      counter.store(0, Ordering::Relaxed);
+    }
+  }
+
+  /// Regions with at least one result, most frequent first.
+  pub fn regions(&self) -> Vec<(Region, usize)> {
+    let mut regions = Region::VARIANTS
+      .iter()
+      .zip(self.regions.iter())
+      .map(|(region, count)| (*region, count.load(Ordering::Relaxed)))
+      .filter(|(_, count)| *count > 0)
+      .collect::<Vec<_>>();
+    regions.sort_by(|(a_region, a), (b_region, b)| b.cmp(a).then(a_region.cmp(b_region)));
+    regions
+  }
+
+  fn draw_counters(&self) -> Vec<Line<'_>> {
+    [
+      (Status::Available.icon(), "Available", &self.available, Color::Green),
+      (Status::Exists.icon(), "Unavailable", &self.unavailable, Color::Red),
+      (Status::Unknown(0).icon(), "Unknown", &self.unknown, Color::Gray),
+      ("❗", "Errors", &self.errors, Color::LightRed),
+    ]
+    .into_iter()
+    .map(|(icon, label, counter, color)| {
+      Line::from(vec![
+        Span::raw(format!(" {icon} ")),
+        Span::raw(format!("{label:<12}")).fg(color),
+        Span::raw(counter.load(Ordering::Relaxed).to_string()).fg(Color::White).bold(),
+      ])
+    })
+    .collect()
+  }
+
+  fn draw_regions(&self, height: u16) -> BarChart<'_> {
+    let bars = self
+      .regions()
+      .into_iter()
+      .take(height as usize)
+      .map(|(region, count)| {
+        Bar::default()
+          .value(count as u64)
+          .label(Line::raw(region.to_string()))
+          .style(Color::Rgb(0, 255, 251))
+      })
+      .collect::<Vec<_>>();
+
+    BarChart::default()
+      .block(blk().title_top(" Regions "))
+      .direction(Direction::Horizontal)
+      .bar_width(1)
+      .bar_gap(0)
+      .data(BarGroup::default().bars(&bars))
+  }
+}
+
+impl Widget for &Breakdown {
+  fn render(self, area: Rect, buf: &mut Buffer)
+  where
+    Self: Sized,
+  {
+    let [counters, regions] = Layout::horizontal([Constraint::Length(24), Constraint::Fill(1)]).areas(area);
+
+    Paragraph::new(self.draw_counters())
+      .block(blk().title_top(" Results "))
+      .render(counters, buf);
+    self.draw_regions(regions.height.saturating_sub(2)).render(regions, buf);
+  }
+}
@@ -1,40 +1,78 @@
-use std::cell::RefCell;
-
-use crossterm::event::Event;
-use helper::keys;
+use crossterm::event::{Event, MouseButton, MouseEventKind};
+use helper::{UnhandledEvent, keys};
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Rect, Spacing};
 use ratatui::prelude::Stylize;
 use ratatui::style::Color;
 use ratatui::text::{Line, Span, Text};
-use ratatui::widgets::{Block, Paragraph, Widget};
+use ratatui::widgets::{Block, Paragraph, Widget, Wrap};
 
-use crate::ui::blk;
+use crate::areas::KnownArea;
+use crate::mouse_area;
+use crate::ui::{blk, clear};
 
-#[derive(Eq, PartialEq)]
+/// Yes/no dialog, `value` is the focused button and the answer once handled.
 pub struct Confirmation {
   prompt: String,
   value: bool,
-  last_render: Option<RefCell<[Rect; 3]>>,
+  confirm_area: KnownArea,
+  cancel_area: KnownArea,
 }
 
 impl Confirmation {
   pub fn new(prompt: String, value: bool) -> Self {
     Self {
       prompt,
-      last_render: None,
       value,
+      confirm_area: Default::default(),
+      cancel_area: Default::default(),
     }
   }
 
-  pub fn handle_event(&mut self, event: Event) -> Option<Event> {
+  pub fn value(&self) -> bool {
+    self.value
+  }
+
+  /// `Handled` carries an answer, `Canceled` means the question was dismissed.
+  pub fn handle_event(&mut self, event: &Event) -> Option<UnhandledEvent> {
     match event {
-      Event::Key(keys!(Char('Y'), SHIFT, Press)) => (),
-      Event::Mouse(_mouse) => (),
-      _ => (),
-    }
+      Event::Key(keys!(Tab, NONE, Press) | keys!(BackTab, SHIFT, Press) | keys!(Left, NONE, Press) | keys!(Right, NONE, Press)) => {
+        self.value = !self.value;
+        Some(UnhandledEvent::render())
+      }
+      Event::Key(keys!(Char('y'), NONE, Press) | keys!(Char('Y'), SHIFT, Press)) => {
+        self.value = true;
+        Some(UnhandledEvent::handled())
+      }
+      Event::Key(keys!(Char('n'), NONE, Press) | keys!(Char('N'), SHIFT, Press)) => {
+        self.value = false;
+        Some(UnhandledEvent::handled())
+      }
+      Event::Key(keys!(Enter, NONE, Press)) => Some(UnhandledEvent::handled()),
+      Event::Key(keys!(Esc, NONE, Press)) => Some(UnhandledEvent::canceled()),
+      Event::Key(_) => Some(UnhandledEvent::no_ops()),
+      Event::Mouse(mouse) => {
+        let position = mouse_area(mouse).as_position();
+        let button = match (self.confirm_area.intersects(position), self.cancel_area.intersects(position)) {
+          (true, _) => true,
+          (_, true) => false,
+          _ => return None,
+        };
 
-    Some(event)
+        match mouse.kind {
+          MouseEventKind::Down(MouseButton::Left) => {
+            self.value = button;
+            Some(UnhandledEvent::handled())
+          }
+          MouseEventKind::Moved if self.value != button => {
+            self.value = button;
+            Some(UnhandledEvent::render())
+          }
+          _ => Some(UnhandledEvent::no_ops()),
+        }
+      }
+      _ => None,
+    }
   }
 
   pub fn area(&self, area: Rect) -> [Rect; 3] {
@@ -44,25 +82,29 @@ impl Confirmation {
       .horizontal_margin(4)
       .split(footer);
 
-    let areas = [input, footer[0], footer[1]];
-
-    if let Some(ref last_render) = self.last_render {
-      last_render.replace(areas);
-    }
-
-    areas
+    [input, footer[0], footer[1]]
   }
 
-  fn confirm_block(&self) -> Block {
-    let spans = vec![Span::raw(" "), Span::raw(self.prompt.as_str()), Span::raw(" ")];
+  fn confirm_block(&self) -> Block<'_> {
+    let spans = vec![
+      Span::raw(" "),
+      Span::raw("[Y/N/TAB/ENTER]").blue(),
+      Span::raw(" Answer "),
+      Span::raw("[ESC]").red(),
+      Span::raw(" Dismiss "),
+    ];
     let line = Line::from(spans).centered();
-    blk().title_top(line).fg(Color::Rgb(255, 123, 0))
+    blk().title_bottom(line).fg(Color::Rgb(255, 123, 0))
   }
 
-  fn draw_button<'a>(&'a self, text: impl Into<Text<'a>>, bg: Color, area: Rect, buf: &mut Buffer) {
-    let block = blk();
-    let text = text.into().centered().fg(Color::White).bg(bg);
-    Paragraph::new(text).block(block).bg(Color::Yellow).render(area, buf)
+  fn draw_button<'a>(&'a self, text: impl Into<Text<'a>>, focused: bool, area: Rect, buf: &mut Buffer) {
+    let (fg, bg) = if focused {
+      (Color::Black, Color::Yellow)
+    } else {
+      (Color::White, Color::Reset)
+    };
+    let text = text.into().centered().fg(fg).bg(bg);
+    Paragraph::new(text).block(blk()).render(area, buf)
   }
 }
 
@@ -71,7 +113,42 @@ impl Widget for &Confirmation {
   where
     Self: Sized,
   {
-    let [input, _confirm, _cancel] = self.area(area);
-    Paragraph::new(self.prompt.as_str()).block(self.confirm_block()).render(input, buf);
+    clear(area, buf);
+    let [input, confirm, cancel] = self.area(area);
+    self.confirm_area.replace(confirm);
+    self.cancel_area.replace(cancel);
+
+    Paragraph::new(self.prompt.as_str())
+      .block(self.confirm_block())
+      .centered()
+      .wrap(Wrap { trim: true })
+      .render(input, buf);
+    self.draw_button("Yes", self.value, confirm, buf);
+    self.draw_button("No", !self.value, cancel, buf);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+  use super::*;
+
+  fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+    Event::Key(KeyEvent::new(code, modifiers))
+  }
+
+  #[test]
+  fn test_handle_event() {
+    let mut confirmation = Confirmation::new(String::from("Exit?"), false);
+
+    let handled = confirmation.handle_event(&key(KeyCode::Tab, KeyModifiers::NONE)).unwrap();
+    assert!(handled.kind.is_render() && confirmation.value());
+
+    let handled = confirmation.handle_event(&key(KeyCode::Char('N'), KeyModifiers::SHIFT)).unwrap();
+    assert!(handled.kind.is_handled() && !confirmation.value());
+
+    let handled = confirmation.handle_event(&key(KeyCode::Esc, KeyModifiers::NONE)).unwrap();
+    assert!(handled.kind.is_canceled());
   }
 }
@@ -0,0 +1,122 @@
+use crossterm::event::{Event, MouseEventKind};
+use helper::{UnhandledEvent, keys};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Stylize, Widget};
+use ratatui::style::Color;
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Paragraph, Wrap};
+
+use crate::areas::KnownArea;
+use crate::mouse_area;
+use crate::ui::{blk, clear};
+
+/// Scrollable key/value view of a single entry.
+pub struct Details {
+  title: String,
+  lines: Vec<Line<'static>>,
+  scroll: u16,
+  known_area: KnownArea,
+}
+
+impl Details {
+  pub fn new(title: impl Into<String>) -> Self {
+    Self {
+      title: title.into(),
+      lines: Vec::new(),
+      scroll: 0,
+      known_area: Default::default(),
+    }
+  }
+
+  pub fn field(mut self, label: &str, value: impl Into<String>) -> Self {
+    self.lines.push(Line::from(vec![
+      Span::raw(format!("{label:<14}")).fg(Color::Cyan),
+      Span::raw(value.into()).fg(Color::White),
+    ]));
+    self
+  }
+
+  pub fn line(mut self, line: impl Into<Line<'static>>) -> Self {
+    self.lines.push(line.into());
+    self
+  }
+
+  pub fn lines(&self) -> &[Line<'static>] {
+    &self.lines
+  }
+
+  fn scroll_by(&mut self, delta: i32) {
+    let max = self.lines.len().saturating_sub(1) as i32;
+    self.scroll = (self.scroll as i32 + delta).clamp(0, max) as u16;
+  }
+
+  pub fn handle_event(&mut self, event: &Event) -> Option<UnhandledEvent> {
+    match event {
+      Event::Key(keys!(Esc, NONE, Press) | keys!(Char('q'), NONE, Press)) => Some(UnhandledEvent::canceled()),
+      Event::Key(keys!(Enter, NONE, Press)) => Some(UnhandledEvent::handled()),
+      Event::Key(keys!(Up, NONE, Press)) => {
+        self.scroll_by(-1);
+        Some(UnhandledEvent::render())
+      }
+      Event::Key(keys!(Down, NONE, Press)) => {
+        self.scroll_by(1);
+        Some(UnhandledEvent::render())
+      }
+      Event::Key(keys!(PageUp, NONE, Press)) => {
+        self.scroll_by(-10);
+        Some(UnhandledEvent::render())
+      }
+      Event::Key(keys!(PageDown, NONE, Press)) => {
+        self.scroll_by(10);
+        Some(UnhandledEvent::render())
+      }
+      Event::Mouse(mouse) if self.known_area.intersects(mouse_area(mouse).as_position()) => match mouse.kind {
+        MouseEventKind::ScrollUp => {
+          self.scroll_by(-1);
+          Some(UnhandledEvent::render())
+        }
+        MouseEventKind::ScrollDown => {
+          self.scroll_by(1);
+          Some(UnhandledEvent::render())
+        }
+        _ => Some(UnhandledEvent::no_ops()),
+      },
+      // Modal: nothing below reacts while the details are open.
+      Event::Key(_) => Some(UnhandledEvent::no_ops()),
+      _ => None,
+    }
+  }
+
+  fn line_controls(&self) -> Line<'_> {
+    let spans = vec![
+      Span::raw(" "),
+      Span::raw("[⬆/⬇/PGUP/PGDN]").blue(),
+      Span::raw(" Scroll "),
+      Span::raw("[ESC/ENTER]").blue(),
+      Span::raw(" Close "),
+    ];
+    Line::from(spans).centered()
+  }
+}
+
+impl Widget for &Details {
+  fn render(self, area: Rect, buf: &mut Buffer)
+  where
+    Self: Sized,
+  {
+    clear(area, buf);
+    self.known_area.replace(area);
+
+    let block = blk()
+      .title_top(Line::raw(format!(" {} ", self.title)).left_aligned())
+      .title_bottom(self.line_controls())
+      .border_style(Color::White);
+
+    Paragraph::new(Text::from(self.lines.clone()))
+      .block(block)
+      .wrap(Wrap { trim: false })
+      .scroll((self.scroll, 0))
+      .render(area, buf);
+  }
+}
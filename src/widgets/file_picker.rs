@@ -0,0 +1,576 @@
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::{env, iter};
+
+use crossterm::event::Event;
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use helper::{UnhandledEvent, keys};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::prelude::{StatefulWidget, Stylize, Widget};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use tokio::task::spawn_blocking;
+
+use crate::areas::KnownArea;
+use crate::ui::{blk, clear};
+use crate::widgets::ticket;
+
+/// Inputs must exist, outputs may be a new name typed into the filter.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PickMode {
+  Open,
+  Save,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Entry {
+  name: String,
+  path: PathBuf,
+  is_dir: bool,
+  size: u64,
+}
+
+/// Size and line count of the highlighted file.
+#[derive(Debug)]
+struct Preview {
+  path: PathBuf,
+  size: u64,
+  /// `None` until the count comes back.
+  lines: Option<io::Result<usize>>,
+  ticket: Option<u64>,
+}
+
+/// A directory listing or a line count on its way, see `FilePicker::loads`.
+pub struct Load {
+  pub ticket: u64,
+  pub loaded: BoxFuture<'static, Loaded>,
+}
+
+/// What a `Load` read, handed back through `FilePicker::deliver`.
+pub struct Loaded(Outcome);
+
+enum Outcome {
+  Listing(Vec<Entry>),
+  Lines(io::Result<usize>),
+}
+
+/// Directory browser returning a path, typing narrows the listing.
+pub struct FilePicker {
+  title: String,
+  mode: PickMode,
+  dir: PathBuf,
+  /// Every entry of `dir`, read once when entering it, `None` until the read comes back.
+  listing: Option<Vec<Entry>>,
+  listing_ticket: Option<u64>,
+  /// Entry to put the cursor on once the listing is in.
+  reselect: Option<PathBuf>,
+  entries: Vec<Entry>,
+  filter: String,
+  show_hidden: bool,
+  selected: usize,
+  preview: Option<Preview>,
+  chosen: Option<PathBuf>,
+  known_area: KnownArea,
+}
+
+impl FilePicker {
+  /// Line counting stops after this many bytes, the preview then shows a lower bound.
+  const PREVIEW_LIMIT: u64 = 4 * 1024 * 1024;
+
+  pub fn new(title: impl Into<String>, mode: PickMode, start: Option<&Path>) -> Self {
+    let dir = start
+      .map(|path| {
+        if path.is_dir() {
+          path.to_path_buf()
+        } else {
+          path.parent().unwrap_or(path).to_path_buf()
+        }
+      })
+      .filter(|dir| dir.is_dir())
+      .or_else(|| env::current_dir().ok())
+      .unwrap_or_else(|| PathBuf::from("."));
+
+    let mut picker = Self {
+      title: title.into(),
+      mode,
+      dir: PathBuf::new(),
+      listing: None,
+      listing_ticket: None,
+      reselect: None,
+      entries: Vec::new(),
+      filter: String::new(),
+      show_hidden: false,
+      selected: 0,
+      preview: None,
+      chosen: None,
+      known_area: Default::default(),
+    };
+    picker.enter(dir);
+    picker
+  }
+
+  /// The chosen path once the picker reported `Handled`.
+  pub fn value(self) -> Option<PathBuf> {
+    self.chosen
+  }
+
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  fn enter(&mut self, dir: PathBuf) {
+    self.dir = fs::canonicalize(&dir).unwrap_or(dir);
+    self.filter.clear();
+    self.selected = 0;
+    self.listing = None;
+    self.listing_ticket = None;
+    self.reselect = None;
+    self.refresh();
+  }
+
+  /// Reads the picker is missing, the owner runs them off the UI task and `deliver`s: a slow disk or a
+  /// large file would otherwise stall every keystroke.
+  pub fn loads(&mut self) -> Vec<Load> {
+    let mut loads = Vec::new();
+    if self.listing.is_none() && self.listing_ticket.is_none() {
+      let ticket = ticket();
+      let dir = self.dir.clone();
+      self.listing_ticket = Some(ticket);
+      loads.push(Load {
+        ticket,
+        loaded: async move { Loaded(Outcome::Listing(spawn_blocking(move || list(&dir)).await.unwrap_or_default())) }.boxed(),
+      });
+    }
+    if let Some(preview) = self
+      .preview
+      .as_mut()
+      .filter(|preview| preview.lines.is_none() && preview.ticket.is_none())
+    {
+      let ticket = ticket();
+      let path = preview.path.clone();
+      preview.ticket = Some(ticket);
+      loads.push(Load {
+        ticket,
+        loaded: async move {
+          let lines = spawn_blocking(move || count_lines(&path, Self::PREVIEW_LIMIT)).await;
+          Loaded(Outcome::Lines(lines.unwrap_or_else(|err| Err(io::Error::other(err)))))
+        }
+        .boxed(),
+      });
+    }
+    loads
+  }
+
+  /// Whether `ticket` reads what the picker still shows, leaving the directory or the file makes it stale.
+  pub fn awaits(&self, ticket: u64) -> bool {
+    self.listing_ticket == Some(ticket) || self.preview.as_ref().is_some_and(|preview| preview.ticket == Some(ticket))
+  }
+
+  /// Takes what `ticket` read if the picker still awaits it.
+  pub fn deliver(&mut self, ticket: u64, loaded: Loaded) -> bool {
+    match loaded.0 {
+      Outcome::Listing(listing) if self.listing_ticket == Some(ticket) => {
+        self.listing_ticket = None;
+        self.listing = Some(listing);
+        self.refresh();
+        let reselected = self
+          .reselect
+          .take()
+          .and_then(|path| self.entries.iter().position(|entry| entry.path == path));
+        if let Some(selected) = reselected {
+          self.selected = selected;
+          self.update_preview();
+        }
+        true
+      }
+      Outcome::Lines(lines) => match &mut self.preview {
+        Some(preview) if preview.ticket == Some(ticket) => {
+          preview.ticket = None;
+          preview.lines = Some(lines);
+          true
+        }
+        _ => false,
+      },
+      Outcome::Listing(_) => false,
+    }
+  }
+
+  fn refresh(&mut self) {
+    let filter = self.filter.to_lowercase();
+    let entries = self
+      .listing
+      .iter()
+      .flatten()
+      .filter(|entry| self.show_hidden || !entry.name.starts_with('.'))
+      .filter(|entry| entry.name.to_lowercase().contains(&filter))
+      .cloned();
+
+    let parent = self.dir.parent().filter(|_| self.filter.is_empty()).map(|parent| Entry {
+      name: String::from(".."),
+      path: parent.to_path_buf(),
+      is_dir: true,
+      size: 0,
+    });
+    self.entries = parent.into_iter().chain(entries).collect();
+    self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+    self.update_preview();
+  }
+
+  fn update_preview(&mut self) {
+    self.preview = match self.entries.get(self.selected) {
+      Some(entry) if !entry.is_dir && self.mode == PickMode::Open => {
+        if self.preview.as_ref().is_some_and(|preview| preview.path == entry.path) {
+          return;
+        }
+        Some(Preview {
+          path: entry.path.clone(),
+          size: entry.size,
+          lines: None,
+          ticket: None,
+        })
+      }
+      _ => None,
+    };
+  }
+
+  fn select(&mut self, delta: isize) {
+    let last = self.entries.len().saturating_sub(1) as isize;
+    self.selected = (self.selected as isize + delta).clamp(0, last.max(0)) as usize;
+    self.update_preview();
+  }
+
+  /// A typed path containing a separator is taken as is, relative to the current directory.
+  fn typed_path(&self) -> Option<PathBuf> {
+    if !self.filter.contains(std::path::MAIN_SEPARATOR) && !self.filter.starts_with('~') {
+      return None;
+    }
+    let path = match self.filter.strip_prefix('~') {
+      Some(rest) => env::home_dir()?.join(rest.trim_start_matches(std::path::MAIN_SEPARATOR)),
+      None => self.dir.join(&self.filter),
+    };
+    Some(path)
+  }
+
+  /// Saving to a typed name only reuses an entry it names exactly, anything else is a new file.
+  fn submit(&mut self) -> UnhandledEvent {
+    let target = match self.typed_path() {
+      Some(path) => Some((path.is_dir(), path)),
+      None if self.mode == PickMode::Save && !self.filter.is_empty() => Some(
+        self
+          .entries
+          .iter()
+          .find(|entry| entry.name == self.filter)
+          .map(|entry| (entry.is_dir, entry.path.clone()))
+          .unwrap_or_else(|| (false, self.dir.join(&self.filter))),
+      ),
+      None => self.entries.get(self.selected).map(|entry| (entry.is_dir, entry.path.clone())),
+    };
+
+    match target {
+      Some((true, dir)) => {
+        self.enter(dir);
+        UnhandledEvent::render()
+      }
+      Some((false, file)) if self.mode == PickMode::Save || file.is_file() => {
+        self.chosen = Some(file);
+        UnhandledEvent::handled()
+      }
+      _ => UnhandledEvent::no_ops(),
+    }
+  }
+
+  pub fn handle_event(&mut self, event: &Event) -> Option<UnhandledEvent> {
+    let Event::Key(key) = event else {
+      return match event {
+        Event::Paste(content) => {
+          self.filter.push_str(content.trim());
+          self.selected = 0;
+          self.refresh();
+          Some(UnhandledEvent::render())
+        }
+        _ => None,
+      };
+    };
+
+    match key {
+      keys!(Esc, NONE, Press) => return Some(UnhandledEvent::canceled()),
+      keys!(Enter, NONE, Press) => return Some(self.submit()),
+      keys!(Up, NONE, Press) => self.select(-1),
+      keys!(Down, NONE, Press) => self.select(1),
+      keys!(PageUp, NONE, Press) => self.select(-10),
+      keys!(PageDown, NONE, Press) => self.select(10),
+      keys!(Home, NONE, Press) => self.select(isize::MIN / 2),
+      keys!(End, NONE, Press) => self.select(isize::MAX / 2),
+      keys!(Backspace, NONE, Press) => {
+        if self.filter.pop().is_some() {
+          self.selected = 0;
+          self.refresh();
+        } else if let Some(parent) = self.dir.parent() {
+          let child = self.dir.clone();
+          self.enter(parent.to_path_buf());
+          // Keep the directory we came from under the cursor.
+          self.reselect = Some(child);
+        }
+      }
+      keys!(Char('t'), CONTROL, Press) => {
+        self.show_hidden = !self.show_hidden;
+        self.refresh();
+      }
+      keys!(Char(chr), NONE, Press) | keys!(Char(chr), SHIFT, Press) => {
+        self.filter.push(*chr);
+        self.selected = 0;
+        self.refresh();
+      }
+      _ => return Some(UnhandledEvent::no_ops()),
+    }
+
+    Some(UnhandledEvent::render())
+  }
+
+  fn draw_entry(entry: &Entry) -> ListItem<'_> {
+    let (icon, size) = if entry.is_dir {
+      ("📁", String::new())
+    } else {
+      ("📄", human_size(entry.size))
+    };
+    let color = if entry.is_dir { Color::Cyan } else { Color::White };
+    ListItem::new(Line::from(vec![
+      Span::raw(format!("{icon} ")),
+      Span::raw(entry.name.as_str()).fg(color),
+      Span::raw(format!(" {size}")).fg(Color::DarkGray),
+    ]))
+  }
+
+  fn draw_preview(&self) -> Line<'_> {
+    match self.preview {
+      _ if self.listing.is_none() => Line::raw("Reading directory...").fg(Color::DarkGray),
+      Some(Preview { size, lines: None, .. }) => Line::raw(format!("{} · counting lines...", human_size(size))).fg(Color::DarkGray),
+      Some(Preview {
+        size,
+        lines: Some(Ok(lines)),
+        ..
+      }) if size > Self::PREVIEW_LIMIT => Line::raw(format!("{} · ≥{lines} lines", human_size(size))).fg(Color::Gray),
+      Some(Preview {
+        size,
+        lines: Some(Ok(lines)),
+        ..
+      }) => Line::raw(format!("{} · {lines} lines", human_size(size))).fg(Color::Gray),
+      Some(Preview {
+        lines: Some(Err(ref err)), ..
+      }) => Line::raw(format!("Unreadable: {err}")).fg(Color::LightRed),
+      None => Line::default(),
+    }
+  }
+
+  fn draw_filter(&self) -> Line<'_> {
+    let hint = match self.mode {
+      PickMode::Open => "Type to filter...",
+      PickMode::Save => "Type to filter or name a new file...",
+    };
+    let hidden = if self.show_hidden { " [hidden shown]" } else { "" };
+    let filter = if self.filter.is_empty() {
+      Span::raw(hint).fg(Color::DarkGray).italic()
+    } else {
+      Span::raw(self.filter.as_str()).fg(Color::White)
+    };
+    Line::from(vec![Span::raw("🔎 "), filter, Span::raw(hidden).fg(Color::DarkGray)])
+  }
+
+  fn line_controls(&self) -> Line<'_> {
+    let spans = vec![
+      Span::raw(" "),
+      Span::raw("[ENTER]").green(),
+      Span::raw(" Open "),
+      Span::raw("[BACKSPACE]").blue(),
+      Span::raw(" Up "),
+      Span::raw("[CTRL+T]").blue(),
+      Span::raw(" Hidden "),
+      Span::raw("[ESC]").red(),
+      Span::raw(" Cancel "),
+    ];
+    Line::from(spans).centered()
+  }
+}
+
+impl Widget for &FilePicker {
+  fn render(self, area: Rect, buf: &mut Buffer)
+  where
+    Self: Sized,
+  {
+    clear(area, buf);
+    self.known_area.replace(area);
+
+    let block = blk()
+      .title_top(Line::raw(format!(" {} ", self.title)).left_aligned().fg(Color::Cyan))
+      .title_top(Line::raw(format!(" {} ", self.dir.display())).right_aligned().fg(Color::DarkGray))
+      .title_bottom(self.line_controls())
+      .border_style(Color::White);
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let [filter, list, preview] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+    Paragraph::new(self.draw_filter()).render(filter, buf);
+    Paragraph::new(self.draw_preview()).render(preview, buf);
+
+    let items = self.entries.iter().map(FilePicker::draw_entry);
+    let list_widget = List::new(items).highlight_style(Style::new().reversed());
+    let mut state = ListState::default().with_selected((!self.entries.is_empty()).then_some(self.selected));
+    StatefulWidget::render(list_widget, list, buf, &mut state);
+  }
+}
+
+/// Entries of `dir` sorted directories first, unreadable ones are skipped.
+/// Links are described by their target, a broken one by the link itself.
+fn list(dir: &Path) -> Vec<Entry> {
+  let mut entries = fs::read_dir(dir)
+    .into_iter()
+    .flatten()
+    .flatten()
+    .filter_map(|entry| {
+      let name = entry.file_name().to_string_lossy().into_owned();
+      let metadata = fs::metadata(entry.path()).or_else(|_| entry.metadata()).ok()?;
+      Some(Entry {
+        name,
+        path: entry.path(),
+        is_dir: metadata.is_dir(),
+        size: metadata.len(),
+      })
+    })
+    .collect::<Vec<_>>();
+  entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase())));
+  entries
+}
+
+fn count_lines(path: &Path, limit: u64) -> io::Result<usize> {
+  let mut reader = File::open(path)?.take(limit);
+  let mut chunk = vec![0; 64 * 1024];
+  let mut lines = 0;
+  loop {
+    match reader.read(&mut chunk)? {
+      0 => return Ok(lines),
+      read => lines += chunk[..read].iter().filter(|byte| **byte == b'\n').count(),
+    }
+  }
+}
+
+pub fn human_size(bytes: u64) -> String {
+  const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+  let (size, unit) = iter::successors(Some(bytes as f64), |size| Some(size / 1024.0))
+    .zip(UNITS)
+    .find(|(size, _)| *size < 1024.0)
+    .unwrap_or((bytes as f64 / 1024f64.powi(4), "TiB"));
+  if unit == "B" {
+    format!("{bytes} B")
+  } else {
+    format!("{size:.1} {unit}")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+  use super::*;
+
+  /// Runs the reads the picker asks for until it shows everything, as the app does between draws.
+  async fn settle(picker: &mut FilePicker) {
+    loop {
+      let loads = picker.loads();
+      if loads.is_empty() {
+        return;
+      }
+      for Load { ticket, loaded } in loads {
+        assert!(picker.deliver(ticket, loaded.await));
+      }
+    }
+  }
+
+  async fn key(picker: &mut FilePicker, code: KeyCode, modifiers: KeyModifiers) -> UnhandledEvent {
+    let unhandled = picker.handle_event(&Event::Key(KeyEvent::new(code, modifiers))).unwrap();
+    settle(picker).await;
+    unhandled
+  }
+
+  fn names(picker: &FilePicker) -> Vec<&str> {
+    picker.entries.iter().map(|entry| entry.name.as_str()).collect()
+  }
+
+  #[tokio::test]
+  async fn test_browse() {
+    let dir = env::temp_dir().join(format!("s3-creator-picker-{}", std::process::id()));
+    _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("nested")).unwrap();
+    fs::write(dir.join("buckets.txt"), "a\nb\nc\n").unwrap();
+    fs::write(dir.join(".hidden"), "").unwrap();
+
+    let mut picker = FilePicker::new("Input", PickMode::Open, Some(&dir.join("buckets.txt")));
+    assert_eq!(picker.draw_preview().to_string(), "Reading directory...");
+    settle(&mut picker).await;
+    assert_eq!(names(&picker), ["..", "nested", "buckets.txt"]);
+
+    key(&mut picker, KeyCode::Char('t'), KeyModifiers::CONTROL).await;
+    assert_eq!(names(&picker), ["..", "nested", ".hidden", "buckets.txt"]);
+
+    key(&mut picker, KeyCode::Char('b'), KeyModifiers::NONE).await;
+    assert_eq!(names(&picker), ["buckets.txt"]);
+    assert_eq!(picker.draw_preview().to_string(), "6 B · 3 lines");
+
+    key(&mut picker, KeyCode::Backspace, KeyModifiers::NONE).await;
+    key(&mut picker, KeyCode::Down, KeyModifiers::NONE).await;
+    key(&mut picker, KeyCode::Enter, KeyModifiers::NONE).await;
+    assert!(picker.dir().ends_with("nested"));
+    key(&mut picker, KeyCode::Backspace, KeyModifiers::NONE).await;
+    assert_eq!(picker.entries[picker.selected].name, "nested");
+
+    key(&mut picker, KeyCode::End, KeyModifiers::NONE).await;
+    assert!(key(&mut picker, KeyCode::Enter, KeyModifiers::NONE).await.kind.is_handled());
+    assert!(picker.value().unwrap().ends_with("buckets.txt"));
+
+    let mut picker = FilePicker::new("Output", PickMode::Save, Some(&dir));
+    settle(&mut picker).await;
+    for chr in "new.jsonl".chars() {
+      key(&mut picker, KeyCode::Char(chr), KeyModifiers::NONE).await;
+    }
+    assert!(key(&mut picker, KeyCode::Enter, KeyModifiers::NONE).await.kind.is_handled());
+    assert!(picker.value().unwrap().ends_with("new.jsonl"));
+
+    // A prefix of an existing file still names a new one.
+    let mut picker = FilePicker::new("Output", PickMode::Save, Some(&dir));
+    settle(&mut picker).await;
+    for chr in "buckets".chars() {
+      key(&mut picker, KeyCode::Char(chr), KeyModifiers::NONE).await;
+    }
+    assert_eq!(names(&picker), ["buckets.txt"]);
+    assert!(key(&mut picker, KeyCode::Enter, KeyModifiers::NONE).await.kind.is_handled());
+    assert_eq!(picker.value().unwrap(), fs::canonicalize(&dir).unwrap().join("buckets"));
+
+    fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[cfg(unix)]
+  #[tokio::test]
+  async fn test_links() {
+    let dir = env::temp_dir().join(format!("s3-creator-picker-links-{}", std::process::id()));
+    _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("nested")).unwrap();
+    std::os::unix::fs::symlink(dir.join("nested"), dir.join("linked")).unwrap();
+    std::os::unix::fs::symlink(dir.join("missing"), dir.join("broken")).unwrap();
+
+    let mut picker = FilePicker::new("Input", PickMode::Open, Some(&dir));
+    settle(&mut picker).await;
+    assert_eq!(names(&picker), ["..", "linked", "nested", "broken"]);
+
+    fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn test_human_size() {
+    assert_eq!(human_size(512), "512 B");
+    assert_eq!(human_size(1536), "1.5 KiB");
+    assert_eq!(human_size(5 * 1024 * 1024), "5.0 MiB");
+  }
+}
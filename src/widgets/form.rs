@@ -0,0 +1,290 @@
+use crossterm::event::{Event, KeyCode, KeyEvent, MouseButton, MouseEventKind};
+use helper::{UnhandledEvent, keys};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect, Spacing};
+use ratatui::prelude::{Stylize, Widget};
+use ratatui::style::Color;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+use crate::areas::KnownArea;
+use crate::mouse_area;
+use crate::ui::{blk, clear};
+use crate::widgets::{Check, Input, Verdict};
+
+mod field;
+pub use field::*;
+
+/// Labeled fields over a submit/cancel footer, Tab and Up/Down move between them.
+/// Focus past the last field lands on the buttons.
+#[derive(Debug)]
+pub struct Form {
+  title: String,
+  fields: Vec<Field>,
+  focus: usize,
+  submit_area: KnownArea,
+  cancel_area: KnownArea,
+}
+
+impl Form {
+  const FOOTER: u16 = 3;
+
+  pub fn new(title: impl Into<String>) -> Self {
+    Self {
+      title: title.into(),
+      fields: Vec::new(),
+      focus: 0,
+      submit_area: Default::default(),
+      cancel_area: Default::default(),
+    }
+  }
+
+  pub fn field(mut self, field: Field) -> Self {
+    self.fields.push(field);
+    self
+  }
+
+  /// Rows needed to show every field, borders and footer included.
+  pub fn height(&self) -> u16 {
+    self.fields.iter().map(Field::height).sum::<u16>() + Self::FOOTER + 2
+  }
+
+  fn get(&self, key: &str) -> Option<&FieldKind> {
+    self.fields.iter().find(|field| field.key == key).map(|field| &field.kind)
+  }
+
+  pub fn text(&self, key: &str) -> Option<&str> {
+    match self.get(key)? {
+      FieldKind::Text(input) => Some(input.text()),
+      _ => None,
+    }
+  }
+
+  pub fn number(&self, key: &str) -> Option<i64> {
+    match self.get(key)? {
+      FieldKind::Spinner { value, .. } => Some(*value),
+      _ => None,
+    }
+  }
+
+  pub fn toggle(&self, key: &str) -> Option<bool> {
+    match self.get(key)? {
+      FieldKind::Toggle(value) => Some(*value),
+      _ => None,
+    }
+  }
+
+  /// Index of the selected option.
+  pub fn choice(&self, key: &str) -> Option<usize> {
+    match self.get(key)? {
+      FieldKind::Select { selected, .. } => Some(*selected),
+      _ => None,
+    }
+  }
+
+  /// Checks still missing for the text fields, see `Input::validation`.
+  pub fn validations(&mut self) -> Vec<Check> {
+    self
+      .fields
+      .iter_mut()
+      .filter_map(Field::input_mut)
+      .filter_map(Input::validation)
+      .collect()
+  }
+
+  pub fn awaits(&self, ticket: u64) -> bool {
+    self.fields.iter().filter_map(Field::input).any(|input| input.awaits(ticket))
+  }
+
+  /// Hands the verdict to the text field awaiting `ticket`.
+  pub fn deliver(&mut self, ticket: u64, verdict: Verdict) -> bool {
+    let awaiting = self.fields.iter_mut().filter_map(Field::input_mut).find(|input| input.awaits(ticket));
+    awaiting.is_some_and(|input| input.deliver(ticket, verdict))
+  }
+
+  fn submit_focus(&self) -> usize {
+    self.fields.len()
+  }
+
+  fn cancel_focus(&self) -> usize {
+    self.fields.len() + 1
+  }
+
+  fn move_focus(&mut self, step: isize) -> UnhandledEvent {
+    let len = self.fields.len() as isize + 2;
+    self.focus = (self.focus as isize + step).rem_euclid(len) as usize;
+    UnhandledEvent::render()
+  }
+
+  /// An invalid field takes the focus and shows its error instead.
+  fn submit(&mut self) -> UnhandledEvent {
+    let Some(invalid) = self.fields.iter().position(|field| !field.is_valid()) else {
+      return UnhandledEvent::handled();
+    };
+    self.focus = invalid;
+    if let Some(input) = self.fields[invalid].input_mut() {
+      input.handle_event(&Event::Key(KeyEvent::from(KeyCode::Enter)));
+    }
+    UnhandledEvent::render()
+  }
+
+  pub fn handle_event(&mut self, event: &Event) -> Option<UnhandledEvent> {
+    // Tab completes in a text field while it has candidates, Up/Down always move on.
+    let completing = self.fields.get(self.focus).and_then(Field::input).is_some_and(Input::can_complete);
+    match event {
+      Event::Key(keys!(Esc, NONE, Press)) => Some(UnhandledEvent::canceled()),
+      Event::Key(keys!(Tab, NONE, Press)) if !completing => Some(self.move_focus(1)),
+      Event::Key(keys!(BackTab, SHIFT, Press)) if !completing => Some(self.move_focus(-1)),
+      Event::Key(keys!(Down, NONE, Press)) => Some(self.move_focus(1)),
+      Event::Key(keys!(Up, NONE, Press)) => Some(self.move_focus(-1)),
+      Event::Key(keys!(Enter, NONE, Press)) if self.focus == self.cancel_focus() => Some(UnhandledEvent::canceled()),
+      Event::Key(keys!(Enter, NONE, Press)) => Some(self.submit()),
+      Event::Key(keys!(Left, NONE, Press) | keys!(Right, NONE, Press)) if self.focus >= self.submit_focus() => {
+        self.focus = if self.focus == self.submit_focus() {
+          self.cancel_focus()
+        } else {
+          self.submit_focus()
+        };
+        Some(UnhandledEvent::render())
+      }
+      Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+        let position = mouse_area(mouse).as_position();
+        if self.submit_area.intersects(position) {
+          self.focus = self.submit_focus();
+          Some(self.submit())
+        } else if self.cancel_area.intersects(position) {
+          Some(UnhandledEvent::canceled())
+        } else {
+          None
+        }
+      }
+      _ => match self.fields.get_mut(self.focus) {
+        Some(field) => field.handle_event(event).or(Some(UnhandledEvent::no_ops())),
+        None => Some(UnhandledEvent::no_ops()),
+      },
+    }
+  }
+
+  fn line_controls(&self) -> Line<'_> {
+    let spans = vec![
+      Span::raw(" "),
+      Span::raw("[TAB/⬆/⬇]").blue(),
+      Span::raw(" Next "),
+      Span::raw("[⬅/➡]").blue(),
+      Span::raw(" Change "),
+      Span::raw("[ENTER]").green(),
+      Span::raw(" Submit "),
+      Span::raw("[ESC]").red(),
+      Span::raw(" Cancel "),
+    ];
+    Line::from(spans).centered()
+  }
+
+  fn draw_button(&self, text: &str, focused: bool, area: Rect, buf: &mut Buffer) {
+    let (fg, bg) = if focused {
+      (Color::Black, Color::Yellow)
+    } else {
+      (Color::White, Color::Reset)
+    };
+    Paragraph::new(Line::raw(text).centered().fg(fg).bg(bg)).block(blk()).render(area, buf)
+  }
+}
+
+impl Widget for &Form {
+  fn render(self, area: Rect, buf: &mut Buffer)
+  where
+    Self: Sized,
+  {
+    clear(area, buf);
+    let block = blk()
+      .title_top(Line::raw(format!(" {} ", self.title)).left_aligned().fg(Color::Cyan))
+      .title_bottom(self.line_controls())
+      .border_style(Color::White);
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let [fields, footer] = Layout::vertical([Constraint::Fill(1), Constraint::Length(Form::FOOTER)]).areas(inner);
+    let rows = Layout::vertical(self.fields.iter().map(|field| Constraint::Length(field.height()))).split(fields);
+    for (index, (field, row)) in self.fields.iter().zip(rows.iter()).enumerate() {
+      field.render_focused(*row, buf, index == self.focus);
+    }
+
+    let [submit, cancel] = Layout::horizontal([Constraint::Fill(1); 2])
+      .spacing(Spacing::Space(4))
+      .horizontal_margin(4)
+      .areas(footer);
+    self.submit_area.replace(submit);
+    self.cancel_area.replace(cancel);
+    self.draw_button("Submit", self.focus == self.submit_focus(), submit, buf);
+    self.draw_button("Cancel", self.focus == self.cancel_focus(), cancel, buf);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crossterm::event::KeyModifiers;
+
+  use super::*;
+  use crate::widgets::{RegionCompleter, ValidRegion};
+
+  fn key(form: &mut Form, code: KeyCode) -> UnhandledEvent {
+    form.handle_event(&Event::Key(KeyEvent::new(code, KeyModifiers::NONE))).unwrap()
+  }
+
+  async fn validate(form: &mut Form) {
+    for Check { ticket, verdict } in form.validations() {
+      assert!(form.deliver(ticket, verdict.await));
+    }
+  }
+
+  #[tokio::test]
+  async fn test_form() {
+    let mut form = Form::new("Settings")
+      .field(Field::spinner("workers", "Workers", 4, 1..=8, 2))
+      .field(Field::toggle("probe", "Probe", false))
+      .field(Field::select("format", "Format", ["Text", "JSON"], 0))
+      .field(Field::text("region", Input::new(None, None).with_text("mars").validator(ValidRegion)));
+
+    key(&mut form, KeyCode::Right);
+    key(&mut form, KeyCode::Right);
+    key(&mut form, KeyCode::Tab);
+    key(&mut form, KeyCode::Char(' '));
+    key(&mut form, KeyCode::Down);
+    key(&mut form, KeyCode::Left);
+    assert_eq!(form.number("workers"), Some(8));
+    assert_eq!(form.toggle("probe"), Some(true));
+    assert_eq!(form.choice("format"), Some(1));
+
+    validate(&mut form).await;
+    form.focus = form.submit_focus();
+    assert!(key(&mut form, KeyCode::Enter).kind.is_render());
+    assert_eq!(form.focus, 3);
+
+    form.handle_event(&Event::Key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL)));
+    form.handle_event(&Event::Paste(String::from("eu-west-1")));
+    validate(&mut form).await;
+    assert!(key(&mut form, KeyCode::Enter).kind.is_handled());
+    assert_eq!(form.text("region"), Some("eu-west-1"));
+  }
+
+  #[test]
+  fn test_tab_completes() {
+    let region = Input::new(None, None).completer(RegionCompleter);
+    let mut form = Form::new("Settings")
+      .field(Field::text("region", region))
+      .field(Field::toggle("probe", "Probe", false));
+    form.handle_event(&Event::Paste(String::from("eu-central")));
+
+    key(&mut form, KeyCode::Tab);
+    key(&mut form, KeyCode::Tab);
+    assert_eq!((form.focus, form.text("region")), (0, Some("eu-central-2")));
+    key(&mut form, KeyCode::Down);
+    assert_eq!(form.focus, 1);
+
+    key(&mut form, KeyCode::Up);
+    form.handle_event(&Event::Key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL)));
+    form.handle_event(&Event::Paste(String::from("us-west-1")));
+    key(&mut form, KeyCode::Tab);
+    assert_eq!(form.focus, 1);
+  }
+}
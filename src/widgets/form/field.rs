@@ -0,0 +1,191 @@
+use std::ops::RangeInclusive;
+
+use crossterm::event::Event;
+use helper::{UnhandledEvent, keys};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::prelude::{Stylize, Widget};
+use ratatui::style::Color;
+use ratatui::text::{Line, Span};
+
+use crate::widgets::Input;
+
+#[derive(Debug)]
+pub enum FieldKind {
+  Text(Box<Input>),
+  Spinner {
+    value: i64,
+    range: RangeInclusive<i64>,
+    step: i64,
+    /// Shown after the value, e.g. `s` or `/s`.
+    unit: &'static str,
+  },
+  Toggle(bool),
+  Select {
+    options: Vec<String>,
+    selected: usize,
+  },
+}
+
+/// One labeled row of a `Form`, found again by its `key`.
+#[derive(Debug)]
+pub struct Field {
+  pub(super) key: &'static str,
+  label: String,
+  pub(super) kind: FieldKind,
+}
+
+impl Field {
+  const LABEL_WIDTH: u16 = 18;
+
+  /// The input's own label is shown on its border.
+  pub fn text(key: &'static str, input: Input) -> Self {
+    Self {
+      key,
+      label: String::new(),
+      kind: FieldKind::Text(Box::new(input)),
+    }
+  }
+
+  pub fn spinner(key: &'static str, label: impl Into<String>, value: i64, range: RangeInclusive<i64>, step: i64) -> Self {
+    Self {
+      key,
+      label: label.into(),
+      kind: FieldKind::Spinner {
+        value: value.clamp(*range.start(), *range.end()),
+        range,
+        step,
+        unit: "",
+      },
+    }
+  }
+
+  pub fn toggle(key: &'static str, label: impl Into<String>, value: bool) -> Self {
+    Self {
+      key,
+      label: label.into(),
+      kind: FieldKind::Toggle(value),
+    }
+  }
+
+  pub fn select<T: ToString>(key: &'static str, label: impl Into<String>, options: impl IntoIterator<Item = T>, selected: usize) -> Self {
+    let options = options.into_iter().map(|option| option.to_string()).collect::<Vec<_>>();
+    Self {
+      key,
+      label: label.into(),
+      kind: FieldKind::Select {
+        selected: selected.min(options.len().saturating_sub(1)),
+        options,
+      },
+    }
+  }
+
+  /// Suffix for spinner values, ignored by other kinds.
+  pub fn unit(mut self, suffix: &'static str) -> Self {
+    if let FieldKind::Spinner { ref mut unit, .. } = self.kind {
+      *unit = suffix;
+    }
+    self
+  }
+
+  pub(super) fn height(&self) -> u16 {
+    match self.kind {
+      // Borders, text and the validation error.
+      FieldKind::Text(_) => 4,
+      _ => 1,
+    }
+  }
+
+  pub(super) fn input(&self) -> Option<&Input> {
+    match self.kind {
+      FieldKind::Text(ref input) => Some(input),
+      _ => None,
+    }
+  }
+
+  pub(super) fn input_mut(&mut self) -> Option<&mut Input> {
+    match self.kind {
+      FieldKind::Text(ref mut input) => Some(input),
+      _ => None,
+    }
+  }
+
+  pub(super) fn is_valid(&self) -> bool {
+    match self.kind {
+      FieldKind::Text(ref input) => input.is_valid(),
+      _ => true,
+    }
+  }
+
+  /// Left/Right change the value of every kind but text, which gets the event as is.
+  pub(super) fn handle_event(&mut self, event: &Event) -> Option<UnhandledEvent> {
+    let Event::Key(key) = event else {
+      return match self.kind {
+        FieldKind::Text(ref mut input) => input.handle_event(event),
+        _ => None,
+      };
+    };
+
+    match (&mut self.kind, key) {
+      (FieldKind::Text(input), _) => input.handle_event(event),
+      (FieldKind::Spinner { value, range, step, .. }, key) => {
+        let delta = match key {
+          keys!(Left, NONE, Press) | keys!(Char('-'), NONE, Press) => -*step,
+          keys!(Right, NONE, Press) | keys!(Char('+'), NONE, Press) | keys!(Char('+'), SHIFT, Press) => *step,
+          keys!(PageDown, NONE, Press) => -*step * 10,
+          keys!(PageUp, NONE, Press) => *step * 10,
+          keys!(Home, NONE, Press) => i64::MIN / 2,
+          keys!(End, NONE, Press) => i64::MAX / 2,
+          _ => return None,
+        };
+        *value = value.saturating_add(delta).clamp(*range.start(), *range.end());
+        Some(UnhandledEvent::render())
+      }
+      (FieldKind::Toggle(value), keys!(Char(' '), NONE, Press) | keys!(Left, NONE, Press) | keys!(Right, NONE, Press)) => {
+        *value = !*value;
+        Some(UnhandledEvent::render())
+      }
+      (FieldKind::Select { options, selected }, key) => {
+        let len = options.len().max(1);
+        *selected = match key {
+          keys!(Left, NONE, Press) => (*selected + len - 1) % len,
+          keys!(Right, NONE, Press) | keys!(Char(' '), NONE, Press) => (*selected + 1) % len,
+          _ => return None,
+        };
+        Some(UnhandledEvent::render())
+      }
+      _ => None,
+    }
+  }
+
+  fn draw_value(&self, focused: bool) -> Line<'_> {
+    let arrows = |text: String| {
+      let (left, right) = if focused { ("◀ ", " ▶") } else { ("  ", "  ") };
+      Line::from(vec![
+        Span::raw(left).fg(Color::DarkGray),
+        Span::raw(text),
+        Span::raw(right).fg(Color::DarkGray),
+      ])
+    };
+
+    match self.kind {
+      FieldKind::Text(_) => Line::default(),
+      FieldKind::Spinner { value, unit, .. } => arrows(format!("{value}{unit}")),
+      FieldKind::Toggle(true) => Line::from(vec![Span::raw("[x] ").green(), Span::raw("On")]),
+      FieldKind::Toggle(false) => Line::from(vec![Span::raw("[ ] ").dark_gray(), Span::raw("Off")]),
+      FieldKind::Select { ref options, selected } => arrows(options.get(selected).cloned().unwrap_or_default()),
+    }
+  }
+
+  pub(super) fn render_focused(&self, area: Rect, buf: &mut Buffer, focused: bool) {
+    if let FieldKind::Text(ref input) = self.kind {
+      input.render_focused(area, buf, focused);
+      return;
+    }
+
+    let [label, value] = Layout::horizontal([Constraint::Length(Self::LABEL_WIDTH), Constraint::Fill(1)]).areas(area);
+    let color = if focused { Color::Yellow } else { Color::Cyan };
+    Line::raw(format!(" {}", self.label)).fg(color).render(label, buf);
+    self.draw_value(focused).render(value, buf);
+  }
+}
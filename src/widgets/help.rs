@@ -0,0 +1,89 @@
+use ratatui::prelude::Stylize;
+use ratatui::style::Color;
+use ratatui::text::{Line, Span};
+use strum::VariantArray;
+
+use crate::config::{Action, Context, Keymap};
+use crate::widgets::{Details, Logs, Results};
+
+/// Every binding of `keymap` by context, `active` being the one keys currently go to.
+pub fn help(keymap: &Keymap, active: Context) -> Details {
+  let mut details = Details::new("⌨ Keys");
+  for context in Context::VARIANTS {
+    if context != &Context::Global {
+      details = details.line("");
+    }
+    let mut title = Span::raw(context.title()).bold().fg(Color::Yellow);
+    if active.layers().contains(context) {
+      title = title.underlined();
+    }
+    details = details.line(title);
+
+    for entry in keymap.entries(*context).filter(|entry| !entry.bindings.is_empty()) {
+      let keys = entry.bindings.iter().map(|binding| binding.label()).collect::<Vec<_>>();
+      details = details.field(&keys.join(", "), entry.action.describe());
+    }
+    for (keys, description) in fixed_keys(*context) {
+      details = details.field(&keys.to_uppercase(), description);
+    }
+  }
+  details
+    .line("")
+    .line(Span::raw("File picker, forms, confirmations and details keep the keys shown in their footers.").fg(Color::DarkGray))
+}
+
+/// Keys the panes handle themselves by position, listed in the help but not rebindable.
+fn fixed_keys(context: Context) -> Vec<(String, &'static str)> {
+  match context {
+    Context::Logs => Logs::fixed_keys(),
+    Context::Results => Results::fixed_keys(),
+    _ => Vec::new(),
+  }
+}
+
+/// One line of the main keys for `context`, processing swaps start for pause.
+pub fn hints(keymap: &Keymap, context: Context, processing: bool) -> Line<'static> {
+  let pane: &[Action] = match context {
+    Context::Logs => &[Action::Search, Action::Details],
+    Context::Results => &[Action::Details, Action::CopySelected],
+    _ => &[],
+  };
+  let run = if processing { Action::Pause } else { Action::Start };
+
+  let actions = [run, Action::ChooseInput, Action::ChooseOutput]
+    .into_iter()
+    .chain(pane.iter().copied())
+    .chain([Action::SwitchPane, Action::RunSettings, Action::Help, Action::Quit]);
+  let spans = actions
+    .filter_map(|action| Some((action, keymap.bindings(context, action).first()?)))
+    .flat_map(|(action, binding)| {
+      [
+        Span::raw(" "),
+        Span::raw(format!("[{}]", binding.label())).blue(),
+        Span::raw(format!(" {}", action.hint())),
+      ]
+    });
+  Line::from(spans.collect::<Vec<_>>())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_generated() {
+    let keymap = Keymap::default();
+    let lines = help(&keymap, Context::Logs).lines().iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert!(lines.iter().any(|line| line.starts_with("s ") && line.ends_with("Start processing")));
+    assert!(lines.iter().any(|line| line.starts_with("0-4 ")));
+    assert!(lines.iter().any(|line| line.starts_with("ALT+1-6 ")));
+    assert!(lines.last().is_some_and(|line| line.starts_with("File picker")));
+
+    let idle = hints(&keymap, Context::Logs, false).to_string();
+    assert!(idle.starts_with(" [s] Start [i] Input"));
+    assert!(idle.contains("[/] Search") && idle.contains("[?] Help"));
+    let running = hints(&keymap, Context::Results, true).to_string();
+    assert!(running.starts_with(" [ESC] Pause"));
+    assert!(!running.contains("Search"));
+  }
+}
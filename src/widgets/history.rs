@@ -0,0 +1,93 @@
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// One-second buckets of completed checks and errors, oldest overwritten first.
+#[derive(Debug, Clone)]
+pub struct History {
+  epoch: Instant,
+  slots: Arc<Mutex<Vec<Slot>>>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Slot {
+  second: u64,
+  completed: u64,
+  errors: u64,
+}
+
+impl Default for History {
+  fn default() -> Self {
+    Self::new(Self::CAPACITY)
+  }
+}
+
+impl History {
+  /// Five minutes of history.
+  pub const CAPACITY: usize = 300;
+
+  pub fn new(capacity: usize) -> Self {
+    Self {
+      epoch: Instant::now(),
+      slots: Arc::new(Mutex::new(vec![Slot::default(); capacity.max(1)])),
+    }
+  }
+
+  fn now(&self) -> u64 {
+    self.epoch.elapsed().as_secs()
+  }
+
+  /// Errors include throttling, they still count as completed checks.
+  pub fn record(&self, error: bool) {
+    self.record_at(self.now(), error);
+  }
+
+  fn record_at(&self, second: u64, error: bool) {
+    let mut slots = self.slots.lock().unwrap();
+    let len = slots.len() as u64;
+    let slot = &mut slots[(second % len) as usize];
+    if slot.second != second {
+      *slot = Slot {
+        second,
+        ..Default::default()
+      };
+    }
+    slot.completed += 1;
+    slot.errors += u64::from(error);
+  }
+
+  /// `(completed, errors)` per second for the last `seconds` seconds, oldest first.
+  pub fn series(&self, seconds: usize) -> (Vec<u64>, Vec<u64>) {
+    self.series_at(self.now(), seconds)
+  }
+
+  fn series_at(&self, now: u64, seconds: usize) -> (Vec<u64>, Vec<u64>) {
+    let slots = self.slots.lock().unwrap();
+    let len = slots.len() as u64;
+    let seconds = (seconds as u64).min(len).min(now + 1);
+
+    (now + 1 - seconds..=now)
+      .map(|second| match slots[(second % len) as usize] {
+        slot if slot.second == second => (slot.completed, slot.errors),
+        _ => (0, 0),
+      })
+      .unzip()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_series_wraps() {
+    let history = History::new(4);
+    history.record_at(0, false);
+    history.record_at(1, true);
+    history.record_at(1, false);
+    assert_eq!(history.series_at(1, 10), (vec![1, 2], vec![0, 1]));
+
+    // Second 1 is overwritten by second 5, seconds 2 to 4 never saw a result.
+    history.record_at(5, false);
+    assert_eq!(history.series_at(5, 4), (vec![0, 0, 0, 1], vec![0, 0, 0, 0]));
+  }
+}
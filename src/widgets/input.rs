@@ -6,119 +6,341 @@ use ratatui::prelude::{Stylize, Widget};
 use ratatui::style::Color;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Paragraph};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use crate::Area;
 use crate::areas::KnownArea;
+use crate::config::{self, Action, Chord, Context, Dispatch};
 use crate::ui::blk;
+use crate::widgets::ticket;
+
+mod buffer;
+pub use buffer::*;
+
+mod completion;
+pub use completion::*;
+
+mod prompt_history;
+pub use prompt_history::*;
+
+mod validation;
+pub use validation::*;
 
 #[derive(Debug)]
 pub struct Input {
-  cursor: usize,
-  value: String,
+  buffer: TextBuffer,
   label: String,
   placeholder: String,
+  completer: Option<Box<dyn Completer>>,
+  candidates: Vec<String>,
+  /// Position in `candidates` while Tab cycles through them.
+  cycle: Option<usize>,
+  history: Option<PromptHistory>,
+  /// Position in `history` while Up/Down recall it, `draft` is what was typed before.
+  recall: Option<usize>,
+  draft: String,
+  validator: Option<Box<dyn Validator>>,
+  /// Verdict and the text it was given for, stale once the text changes.
+  validation: Option<(String, Verdict)>,
+  /// Ticket and text of the check still running.
+  pending: Option<(u64, String)>,
+  /// Errors stay hidden until the first edit or submit attempt.
+  touched: bool,
+  chord: Chord,
   known_area: KnownArea,
 }
 
 impl Input {
   pub fn new(label: impl Into<Option<String>>, placeholder: impl Into<Option<String>>) -> Self {
     Self {
-      cursor: 0,
-      value: String::new(),
+      buffer: TextBuffer::default(),
       label: label.into().unwrap_or(String::from("Input")).to_string(),
       placeholder: placeholder.into().unwrap_or(String::from("Start typing..")),
+      completer: None,
+      candidates: Vec::new(),
+      cycle: None,
+      history: None,
+      recall: None,
+      draft: String::new(),
+      validator: None,
+      validation: None,
+      pending: None,
+      touched: false,
+      chord: Chord::default(),
       known_area: Default::default(),
     }
   }
-  pub fn handle_event(&mut self, event: &Event) -> Option<UnhandledEvent> {
-    match event {
-      Event::Key(keys!(Backspace, NONE, Press)) => {
-        if self.cursor == 0 || self.value.is_empty() {
-          return Some(UnhandledEvent::no_ops());
-        }
 
-        // Convert to char indices for proper Unicode handling
-        let mut chars: Vec<char> = self.value.chars().collect();
-        if self.cursor > 0 && self.cursor <= chars.len() {
-          chars.remove(self.cursor - 1);
-          self.value = chars.into_iter().collect();
-          self.cursor -= 1;
-        }
+  pub fn completer(mut self, completer: impl Completer + 'static) -> Self {
+    self.completer = Some(Box::new(completer));
+    self.refresh_candidates();
+    self
+  }
 
-        Some(UnhandledEvent::render())
+  /// Submitting is refused until `validator` accepts the text, see `validation`.
+  pub fn validator(mut self, validator: impl Validator + 'static) -> Self {
+    self.validator = Some(Box::new(validator));
+    self
+  }
+
+  /// The check of the current text unless its verdict is in or on its way, the owner runs it and `deliver`s.
+  pub fn validation(&mut self) -> Option<Check> {
+    let validator = self.validator.as_ref()?;
+    let text = self.buffer.text();
+    let judged = self.validation.as_ref().is_some_and(|(validated, _)| validated == text);
+    let awaited = self.pending.as_ref().is_some_and(|(_, checked)| checked == text);
+    if judged || awaited {
+      return None;
+    }
+    let ticket = ticket();
+    self.pending = Some((ticket, text.to_string()));
+    Some(Check {
+      ticket,
+      verdict: validator.validate(text),
+    })
+  }
+
+  /// Whether the verdict of `ticket` is still wanted, later edits make it stale.
+  pub fn awaits(&self, ticket: u64) -> bool {
+    self.pending.as_ref().is_some_and(|(pending, _)| *pending == ticket)
+  }
+
+  /// Takes the verdict of `ticket` if this input still awaits it.
+  pub fn deliver(&mut self, ticket: u64, verdict: Verdict) -> bool {
+    match self.pending.take_if(|(pending, _)| *pending == ticket) {
+      Some((_, text)) => {
+        self.validation = Some((text, verdict));
+        true
       }
-      Event::Key(keys!(Delete, NONE, Press)) => {
-        if self.value.is_empty() {
-          return Some(UnhandledEvent::no_ops());
-        }
+      None => false,
+    }
+  }
 
-        let mut chars: Vec<char> = self.value.chars().collect();
-        if self.cursor < chars.len() {
-          chars.remove(self.cursor);
-          self.value = chars.into_iter().collect();
-        }
+  /// Valid once the verdict on the current text is in and positive.
+  pub fn is_valid(&self) -> bool {
+    match (&self.validator, &self.validation) {
+      (None, _) => true,
+      (Some(_), Some((validated, verdict))) => validated == self.buffer.text() && verdict.is_ok(),
+      (Some(_), None) => false,
+    }
+  }
+
+  fn error(&self) -> Option<&str> {
+    match (&self.validation, &self.pending) {
+      (Some((validated, Err(error))), _) if self.touched && validated == self.buffer.text() => Some(error),
+      (_, Some((_, checked))) if self.touched && checked == self.buffer.text() => Some("Checking..."),
+      _ => None,
+    }
+  }
+
+  /// Starts with `text` instead of the placeholder.
+  pub fn with_text(mut self, text: impl Into<String>) -> Self {
+    self.buffer = TextBuffer::new(text);
+    self.refresh_candidates();
+    self
+  }
+
+  /// Recalls and records submitted values under `key`, shared by every prompt using the same key.
+  pub fn history(mut self, key: &str) -> Self {
+    self.history = Some(PromptHistory::load(key));
+    self
+  }
 
+  pub fn handle_event(&mut self, event: &Event) -> Option<UnhandledEvent> {
+    let action = match event {
+      Event::Key(key) => match config::current().keys.dispatch(Context::Input, &mut self.chord, *key) {
+        Dispatch::Action(action) => Some(action),
+        Dispatch::Pending => return Some(UnhandledEvent::no_ops()),
+        Dispatch::Unbound => None,
+      },
+      _ => None,
+    };
+
+    match action {
+      Some(Action::Complete) => Some(self.cycle_candidates(1)),
+      Some(Action::CompletePrevious) => Some(self.cycle_candidates(-1)),
+      Some(Action::HistoryPrevious) => Some(self.recall_previous()),
+      Some(Action::HistoryNext) => Some(self.recall_next()),
+      Some(Action::Right | Action::End) if self.suggestion().is_some() => {
+        let candidate = self.candidates[0].clone();
+        self.set_value(candidate);
+        self.typed();
         Some(UnhandledEvent::render())
       }
-      Event::Key(keys!(Enter, NONE, Press)) => Some(UnhandledEvent::handled()),
-      Event::Key(keys!(Esc, NONE, Press)) => Some(UnhandledEvent::canceled()),
-      Event::Key(keys!(Left, NONE, Press)) => {
-        self.cursor = self.cursor.saturating_sub(1);
+      Some(Action::Submit) if !self.is_valid() => {
+        self.touched = true;
         Some(UnhandledEvent::render())
       }
-      Event::Key(keys!(Right, NONE, Press)) => {
-        let max_cursor = self.value.chars().count();
-        if self.cursor < max_cursor {
-          self.cursor += 1;
+      Some(Action::Submit) => {
+        if let Some(history) = &mut self.history {
+          // Losing the history file is not worth interrupting the prompt.
+          _ = history.push(self.buffer.text());
         }
-        Some(UnhandledEvent::render())
-      }
-      Event::Key(keys!(Home, NONE, Press)) => {
-        self.cursor = 0;
-        Some(UnhandledEvent::render())
+        Some(UnhandledEvent::handled())
       }
-      Event::Key(keys!(End, NONE, Press)) => {
-        self.cursor = self.value.chars().count();
-        Some(UnhandledEvent::render())
+      Some(Action::Cancel) => Some(UnhandledEvent::canceled()),
+      _ => {
+        let before = self.buffer.text().to_string();
+        let handled = self.edit(action, event);
+        if self.buffer.text() != before {
+          self.typed();
+        }
+        handled
       }
-      Event::Key(keys!(Char(chr), NONE, Press)) => {
-        self.insert_char(*chr);
-        Some(UnhandledEvent::render())
+    }
+  }
+
+  /// Readline editing bound in the input context, unbound characters are typed.
+  fn edit(&mut self, action: Option<Action>, event: &Event) -> Option<UnhandledEvent> {
+    let buffer = &mut self.buffer;
+    let changed = match (action, event) {
+      (None, Event::Key(keys!(Char(chr), NONE, Press) | keys!(Char(chr), SHIFT, Press))) => {
+        buffer.insert(chr.encode_utf8(&mut [0; 4]));
+        true
       }
-      Event::Paste(content) => {
-        self.paste(content);
-        Some(UnhandledEvent::render())
+      (None, Event::Paste(content)) => {
+        buffer.insert(&content.replace(['\r', '\n'], ""));
+        true
       }
-      _ => None,
+      (Some(Action::DeleteBackward), _) => buffer.backspace(),
+      (Some(Action::DeleteForward), _) => buffer.delete(),
+      (Some(Action::KillWordBackward), _) => buffer.kill_word_backward(),
+      (Some(Action::KillWordForward), _) => buffer.kill_word_forward(),
+      (Some(Action::KillToStart), _) => buffer.kill_to_start(),
+      (Some(Action::KillToEnd), _) => buffer.kill_to_end(),
+      (Some(Action::Yank), _) => buffer.yank(),
+      (Some(Action::YankPop), _) => buffer.yank_pop(),
+      (Some(Action::Undo), _) => buffer.undo(),
+      (Some(Action::Redo), _) => buffer.redo(),
+      (Some(Action::Left), _) => touch(|| buffer.left(false)),
+      (Some(Action::Right), _) => touch(|| buffer.right(false)),
+      (Some(Action::WordLeft), _) => touch(|| buffer.word_left(false)),
+      (Some(Action::WordRight), _) => touch(|| buffer.word_right(false)),
+      (Some(Action::Home), _) => touch(|| buffer.home(false)),
+      (Some(Action::End), _) => touch(|| buffer.end(false)),
+      (Some(Action::SelectLeft), _) => touch(|| buffer.left(true)),
+      (Some(Action::SelectRight), _) => touch(|| buffer.right(true)),
+      (Some(Action::SelectWordLeft), _) => touch(|| buffer.word_left(true)),
+      (Some(Action::SelectWordRight), _) => touch(|| buffer.word_right(true)),
+      (Some(Action::SelectHome), _) => touch(|| buffer.home(true)),
+      (Some(Action::SelectEnd), _) => touch(|| buffer.end(true)),
+      _ => return None,
+    };
+
+    match changed {
+      true => Some(UnhandledEvent::render()),
+      false => Some(UnhandledEvent::no_ops()),
     }
   }
-  fn insert_char(&mut self, chr: char) {
-    let mut chars: Vec<char> = self.value.chars().collect();
-    chars.insert(self.cursor, chr);
-    self.value = chars.into_iter().collect();
-    self.cursor += 1;
+
+  /// Any edit ends cycling and recalling, the suggestions follow the new text.
+  fn typed(&mut self) {
+    self.touched = true;
+    self.cycle = None;
+    self.recall = None;
+    self.refresh_candidates();
+  }
+
+  fn refresh_candidates(&mut self) {
+    self.candidates = match &mut self.completer {
+      Some(completer) => completer.complete(self.buffer.text()),
+      None => Vec::new(),
+    };
+  }
+
+  fn set_value(&mut self, value: String) {
+    self.buffer.set(value);
+  }
+
+  /// Whether completing would change anything, a lone candidate equal to the text has nothing left to add.
+  pub fn can_complete(&self) -> bool {
+    self.cycle.is_some() || self.candidates.iter().any(|candidate| candidate != self.buffer.text())
+  }
+
+  /// Ghost text completing the first candidate, only while typing at the end.
+  fn suggestion(&self) -> Option<&str> {
+    if self.cycle.is_some() || self.buffer.is_empty() || !self.buffer.at_end() {
+      return None;
+    }
+    let rest = self.candidates.first()?.strip_prefix(self.buffer.text())?;
+    (!rest.is_empty()).then_some(rest)
+  }
+
+  /// A single candidate is accepted outright so completion can continue from it.
+  fn cycle_candidates(&mut self, step: isize) -> UnhandledEvent {
+    let len = self.candidates.len();
+    if len == 0 {
+      return UnhandledEvent::no_ops();
+    }
+
+    let index = match self.cycle {
+      None if step > 0 => 0,
+      None => len - 1,
+      Some(index) => (index as isize + step).rem_euclid(len as isize) as usize,
+    };
+    self.set_value(self.candidates[index].clone());
+    if len == 1 {
+      self.typed();
+    } else {
+      self.cycle = Some(index);
+    }
+    UnhandledEvent::render()
   }
 
-  fn paste(&mut self, content: impl AsRef<str>) {
-    let content_str = content.as_ref();
-    let mut chars: Vec<char> = self.value.chars().collect();
+  fn recall_previous(&mut self) -> UnhandledEvent {
+    let Some(history) = &self.history else {
+      return UnhandledEvent::no_ops();
+    };
+    let index = match self.recall {
+      _ if history.is_empty() => return UnhandledEvent::no_ops(),
+      None => {
+        self.draft = self.buffer.text().to_string();
+        history.len() - 1
+      }
+      Some(index) => index.saturating_sub(1),
+    };
+    let value = history.get(index).unwrap_or_default().to_string();
+    self.recalled(Some(index), value)
+  }
 
-    for (i, chr) in content_str.chars().enumerate() {
-      chars.insert(self.cursor + i, chr);
+  fn recall_next(&mut self) -> UnhandledEvent {
+    let (Some(history), Some(index)) = (&self.history, self.recall) else {
+      return UnhandledEvent::no_ops();
+    };
+    match history.get(index + 1) {
+      Some(value) => {
+        let value = value.to_string();
+        self.recalled(Some(index + 1), value)
+      }
+      None => {
+        let draft = std::mem::take(&mut self.draft);
+        self.recalled(None, draft)
+      }
     }
+  }
 
-    self.value = chars.into_iter().collect();
-    self.cursor += content_str.chars().count();
+  fn recalled(&mut self, recall: Option<usize>, value: String) -> UnhandledEvent {
+    self.set_value(value);
+    self.cycle = None;
+    self.refresh_candidates();
+    self.recall = recall;
+    UnhandledEvent::render()
   }
 
   pub fn value(self) -> String {
-    self.value
+    self.buffer.into_string()
+  }
+
+  pub fn text(&self) -> &str {
+    self.buffer.text()
   }
-  fn draw_label(&self) -> Line {
+
+  fn draw_label(&self) -> Line<'_> {
     Line::raw(&self.label).left_aligned().fg(Color::Cyan)
   }
 
-  fn draw_block(&self) -> Block {
+  fn draw_block(&self) -> Block<'_> {
     use ratatui::symbols::block::ONE_EIGHTH as I;
 
     let submit: Line = [
@@ -133,94 +355,76 @@ impl Input {
       Span::raw(" "),
       Span::raw(I),
       Span::raw(" "),
-      Span::raw("[⬅/➡/HOME/END]").fg(Color::Blue),
-      Span::raw(" Navigate"),
+      Span::raw("[CTRL+W/U/K/Y/Z]").fg(Color::Blue),
+      Span::raw(" Edit"),
       Span::raw(" "),
     ]
     .into_iter()
+    .chain(self.completer.as_ref().map(|_| Span::raw("[TAB]").fg(Color::Blue)))
+    .chain(self.completer.as_ref().map(|_| Span::raw(" Complete ")))
+    .chain(self.history.as_ref().map(|_| Span::raw("[⬆/⬇]").fg(Color::Blue)))
+    .chain(self.history.as_ref().map(|_| Span::raw(" History ")))
     .collect();
 
-    blk()
-      .title_top(self.draw_label())
-      .title_bottom(submit.centered())
-      .border_style(Color::White)
+    let border = if self.error().is_some() { Color::LightRed } else { Color::White };
+    blk().title_top(self.draw_label()).title_bottom(submit.centered()).border_style(border)
   }
 
-  fn calculate_scroll_offset(&self, inner_width: usize) -> usize {
-    if inner_width == 0 {
-      return 0;
-    }
-
-    // Reserve space for cursor
-    let available_width = inner_width.saturating_sub(1);
-
-    if self.cursor < available_width {
-      0
-    } else {
-      self.cursor.saturating_sub(available_width)
-    }
-  }
   #[inline(always)]
-  fn cursor(&self) -> Span {
+  fn cursor(&self) -> Span<'_> {
     Span::raw("│").fg(Color::Yellow).bg(Color::DarkGray)
   }
-  fn draw_input(&self, area: Area, buf: &mut Buffer) {
-    let block = self.draw_block();
-    let inner = block.inner(area);
 
-    // Calculate available width for text
-    let inner_width = inner.width as usize;
-    let scroll_offset = self.calculate_scroll_offset(inner_width);
+  /// Scrolls horizontally so the cursor stays visible, wide graphemes count for their display width.
+  fn draw_text(&self, width: usize, focused: bool) -> Vec<Span<'_>> {
+    let text = self.buffer.text();
+    let cursor = self.buffer.cursor();
+    let selection = self.buffer.selection().unwrap_or_default();
+    // One column is taken by the cursor itself.
+    let available = width.saturating_sub(1);
+    let offset = text[..cursor].width().saturating_sub(available);
 
-    let spans = if self.value.is_empty() {
-      // Show placeholder and cursor
-      vec![Span::from(self.placeholder.as_str()).fg(Color::DarkGray).italic(), self.cursor()]
-    } else {
-      let chars: Vec<char> = self.value.chars().collect();
-      let mut result_spans = Vec::new();
-
-      // Apply horizontal scrolling
-      let visible_start = scroll_offset;
-      let visible_end = (scroll_offset + inner_width.saturating_sub(1)).min(chars.len());
-
-      // Determine cursor position relative to visible area
-      let cursor_in_view = self.cursor >= visible_start && self.cursor <= visible_end;
-      let relative_cursor = self.cursor.saturating_sub(visible_start);
-
-      if visible_start < chars.len() {
-        // Add visible text before cursor
-        if cursor_in_view && relative_cursor > 0 {
-          let before_cursor: String = chars[visible_start..visible_start + relative_cursor].iter().collect();
-          if !before_cursor.is_empty() {
-            result_spans.push(Span::from(before_cursor).fg(Color::White));
-          }
-        }
-
-        // Add cursor
-        if cursor_in_view {
-          result_spans.push(self.cursor());
-        }
-
-        // Add visible text after cursor
-        if cursor_in_view && visible_start + relative_cursor < visible_end {
-          let after_cursor: String = chars[visible_start + relative_cursor..visible_end].iter().collect();
-          if !after_cursor.is_empty() {
-            result_spans.push(Span::from(after_cursor).fg(Color::White));
-          }
-        } else if !cursor_in_view {
-          // Cursor is not in view, just show the visible text
-          let visible_text: String = chars[visible_start..visible_end].iter().collect();
-          result_spans.push(Span::from(visible_text).fg(Color::White));
-        }
-      } else if cursor_in_view {
-        // Only cursor is visible (at end of text)
-        result_spans.push(self.cursor());
+    let mut spans = Vec::new();
+    let mut column = 0;
+    for (index, grapheme) in text.grapheme_indices(true) {
+      if focused && index == cursor {
+        spans.push(self.cursor());
+      }
+      let grapheme_width = grapheme.width();
+      column += grapheme_width;
+      if column <= offset {
+        continue;
       }
+      if column > offset + available {
+        break;
+      }
+      let span = Span::raw(grapheme).fg(Color::White);
+      spans.push(if selection.contains(&index) { span.reversed() } else { span });
+    }
+    if focused && cursor == text.len() {
+      spans.push(self.cursor());
+    }
+    spans
+  }
 
-      result_spans
+  fn draw_input(&self, area: Area, buf: &mut Buffer, block: Block<'_>, focused: bool) {
+    let inner = block.inner(area);
+
+    let mut spans = if self.buffer.is_empty() {
+      let placeholder = Span::from(self.placeholder.as_str()).fg(Color::DarkGray).italic();
+      [placeholder].into_iter().chain(focused.then(|| self.cursor())).collect()
+    } else {
+      self.draw_text(inner.width as usize, focused)
     };
 
-    Paragraph::new(Line::from_iter(spans)).block(block).render(area, buf);
+    // The cursor is the last span whenever there is a suggestion.
+    if let Some(rest) = self.suggestion().filter(|_| focused) {
+      spans.push(Span::from(rest).fg(Color::DarkGray));
+    }
+
+    let error = self.error().map(|error| Line::raw(format!("✗ {error}")).fg(Color::LightRed));
+    let lines = [Line::from_iter(spans)].into_iter().chain(error).collect::<Vec<_>>();
+    Paragraph::new(lines).block(block).render(area, buf);
   }
 }
 
@@ -230,6 +434,86 @@ impl Widget for &Input {
     Self: Sized,
   {
     self.known_area.replace(area);
-    self.draw_input(area, buf)
+    self.draw_input(area, buf, self.draw_block(), true)
+  }
+}
+
+impl Input {
+  /// As a field of a larger popup: no controls, the border tells focus and errors apart.
+  pub fn render_focused(&self, area: Rect, buf: &mut Buffer, focused: bool) {
+    self.known_area.replace(area);
+    let border = match (self.error(), focused) {
+      (Some(_), _) => Color::LightRed,
+      (None, true) => Color::Yellow,
+      (None, false) => Color::White,
+    };
+    let block = blk().title_top(self.draw_label()).border_style(border);
+    self.draw_input(area, buf, block, focused)
+  }
+}
+
+/// Movements always redraw, the cursor or the selection changed.
+fn touch(movement: impl FnOnce()) -> bool {
+  movement();
+  true
+}
+
+#[cfg(test)]
+mod tests {
+  use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+  use super::*;
+
+  fn key(input: &mut Input, code: KeyCode, modifiers: KeyModifiers) {
+    input.handle_event(&Event::Key(KeyEvent::new(code, modifiers)));
+  }
+
+  #[test]
+  fn test_complete_and_recall() {
+    let mut input = Input::new(None, None).completer(RegionCompleter);
+    input.handle_event(&Event::Paste(String::from("eu-central")));
+    assert_eq!(input.suggestion(), Some("-1"));
+
+    key(&mut input, KeyCode::BackTab, KeyModifiers::SHIFT);
+    assert_eq!(input.text(), "eu-central-2");
+    key(&mut input, KeyCode::Tab, KeyModifiers::NONE);
+    assert_eq!(input.text(), "eu-central-1");
+
+    let mut input = Input::new(None, None);
+    input.history = Some(PromptHistory::default());
+    for value in ["first", "second"] {
+      input.set_value(value.to_string());
+      key(&mut input, KeyCode::Enter, KeyModifiers::NONE);
+    }
+    input.set_value(String::from("draft"));
+    key(&mut input, KeyCode::Up, KeyModifiers::NONE);
+    key(&mut input, KeyCode::Up, KeyModifiers::NONE);
+    key(&mut input, KeyCode::Up, KeyModifiers::NONE);
+    assert_eq!(input.text(), "first");
+    key(&mut input, KeyCode::Down, KeyModifiers::NONE);
+    key(&mut input, KeyCode::Down, KeyModifiers::NONE);
+    assert_eq!(input.text(), "draft");
+  }
+
+  #[tokio::test]
+  async fn test_refuse_invalid() {
+    let mut input = Input::new(None, None).validator(ValidRegion);
+    input.handle_event(&Event::Paste(String::from("eu-west")));
+    let check = input.validation().unwrap();
+    assert!(input.validation().is_none());
+    let enter = Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(input.handle_event(&enter).unwrap().kind.is_render());
+    assert_eq!(input.error(), Some("Checking..."));
+    assert!(input.deliver(check.ticket, check.verdict.await));
+    assert!(input.error().is_some_and(|error| error.starts_with("Unknown region")));
+
+    input.handle_event(&Event::Paste(String::from("-1")));
+    assert!(!input.is_valid());
+    let stale = input.validation().unwrap();
+    input.handle_event(&Event::Paste(String::from(" ")));
+    let check = input.validation().unwrap();
+    assert!(!input.deliver(stale.ticket, stale.verdict.await));
+    assert!(input.deliver(check.ticket, check.verdict.await));
+    assert!(input.handle_event(&enter).unwrap().kind.is_handled());
   }
 }
@@ -0,0 +1,382 @@
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Debug, Clone, Default)]
+struct Snapshot {
+  text: String,
+  cursor: usize,
+  anchor: Option<usize>,
+}
+
+/// The previous edit, it decides what the next one merges with.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Last {
+  Insert,
+  Delete,
+  Kill,
+  /// Text yanked from `index` in the kill ring, starting at `start`.
+  Yank {
+    start: usize,
+    index: usize,
+  },
+}
+
+/// Single line text edited in place, positions are byte offsets kept on grapheme boundaries.
+#[derive(Debug, Clone, Default)]
+pub struct TextBuffer {
+  text: String,
+  cursor: usize,
+  /// Other end of the selection, which spans up to `cursor`.
+  anchor: Option<usize>,
+  undo: Vec<Snapshot>,
+  redo: Vec<Snapshot>,
+  last: Option<Last>,
+  /// Killed text, most recent first.
+  kill_ring: VecDeque<String>,
+}
+
+impl TextBuffer {
+  const KILL_RING: usize = 16;
+  const UNDO: usize = 100;
+
+  /// Starts with `text` and the cursor at its end, nothing to undo.
+  pub fn new(text: impl Into<String>) -> Self {
+    let text = text.into();
+    Self {
+      cursor: text.len(),
+      text,
+      ..Default::default()
+    }
+  }
+
+  pub fn text(&self) -> &str {
+    &self.text
+  }
+
+  pub fn into_string(self) -> String {
+    self.text
+  }
+
+  pub fn cursor(&self) -> usize {
+    self.cursor
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.text.is_empty()
+  }
+
+  pub fn at_end(&self) -> bool {
+    self.cursor == self.text.len() && self.anchor.is_none()
+  }
+
+  pub fn selection(&self) -> Option<Range<usize>> {
+    let anchor = self.anchor.filter(|anchor| *anchor != self.cursor)?;
+    Some(anchor.min(self.cursor)..anchor.max(self.cursor))
+  }
+
+  /// Replaces the whole text as one undoable step, the cursor goes to the end.
+  pub fn set(&mut self, text: impl Into<String>) {
+    self.record(None);
+    self.text = text.into();
+    self.cursor = self.text.len();
+    self.anchor = None;
+  }
+
+  /// Typed or pasted text replaces the selection, consecutive typing is undone at once.
+  pub fn insert(&mut self, text: &str) {
+    let coalesce = self.selection().is_none() && !text.contains(char::is_whitespace);
+    self.record(coalesce.then_some(Last::Insert));
+    self.put(text);
+  }
+
+  fn put(&mut self, text: &str) {
+    self.delete_selection();
+    self.text.insert_str(self.cursor, text);
+    self.cursor += text.len();
+  }
+
+  pub fn backspace(&mut self) -> bool {
+    self.delete_to(self.prev_boundary(self.cursor))
+  }
+
+  pub fn delete(&mut self) -> bool {
+    self.delete_to(self.next_boundary(self.cursor))
+  }
+
+  fn delete_to(&mut self, position: usize) -> bool {
+    if self.selection().is_none() && position == self.cursor {
+      return false;
+    }
+    self.record(self.selection().is_none().then_some(Last::Delete));
+    if !self.delete_selection() {
+      self.remove(self.cursor.min(position)..self.cursor.max(position));
+    }
+    true
+  }
+
+  fn delete_selection(&mut self) -> bool {
+    let Some(range) = self.selection() else {
+      self.anchor = None;
+      return false;
+    };
+    self.remove(range);
+    true
+  }
+
+  fn remove(&mut self, range: Range<usize>) -> String {
+    self.cursor = range.start;
+    self.anchor = None;
+    self.text.drain(range).collect()
+  }
+
+  /// Moves the cursor, `select` extends the selection instead of dropping it.
+  pub fn move_to(&mut self, position: usize, select: bool) {
+    match select {
+      true => _ = self.anchor.get_or_insert(self.cursor),
+      false => self.anchor = None,
+    }
+    self.cursor = position;
+    self.last = None;
+  }
+
+  pub fn left(&mut self, select: bool) {
+    let position = match self.selection() {
+      Some(range) if !select => range.start,
+      _ => self.prev_boundary(self.cursor),
+    };
+    self.move_to(position, select);
+  }
+
+  pub fn right(&mut self, select: bool) {
+    let position = match self.selection() {
+      Some(range) if !select => range.end,
+      _ => self.next_boundary(self.cursor),
+    };
+    self.move_to(position, select);
+  }
+
+  pub fn word_left(&mut self, select: bool) {
+    self.move_to(self.prev_word(self.cursor), select);
+  }
+
+  pub fn word_right(&mut self, select: bool) {
+    self.move_to(self.next_word(self.cursor), select);
+  }
+
+  pub fn home(&mut self, select: bool) {
+    self.move_to(0, select);
+  }
+
+  pub fn end(&mut self, select: bool) {
+    self.move_to(self.text.len(), select);
+  }
+
+  pub fn kill_word_backward(&mut self) -> bool {
+    self.kill(self.prev_word(self.cursor)..self.cursor, true)
+  }
+
+  pub fn kill_word_forward(&mut self) -> bool {
+    self.kill(self.cursor..self.next_word(self.cursor), false)
+  }
+
+  pub fn kill_to_start(&mut self) -> bool {
+    self.kill(0..self.cursor, true)
+  }
+
+  pub fn kill_to_end(&mut self) -> bool {
+    self.kill(self.cursor..self.text.len(), false)
+  }
+
+  /// The selection, when there is one, is killed instead of `range`.
+  /// Consecutive kills build a single ring entry.
+  fn kill(&mut self, range: Range<usize>, backward: bool) -> bool {
+    let range = self.selection().unwrap_or(range);
+    if range.is_empty() {
+      return false;
+    }
+
+    let merge = self.last == Some(Last::Kill);
+    self.record(None);
+    let killed = self.remove(range);
+    match self.kill_ring.front_mut() {
+      Some(front) if merge && backward => front.insert_str(0, &killed),
+      Some(front) if merge => front.push_str(&killed),
+      _ => {
+        self.kill_ring.push_front(killed);
+        self.kill_ring.truncate(Self::KILL_RING);
+      }
+    }
+    self.last = Some(Last::Kill);
+    true
+  }
+
+  /// Undone on its own, never together with the typing around it.
+  pub fn yank(&mut self) -> bool {
+    let Some(killed) = self.kill_ring.front().cloned() else {
+      return false;
+    };
+    self.record(None);
+    self.put(&killed);
+    self.last = Some(Last::Yank {
+      start: self.cursor - killed.len(),
+      index: 0,
+    });
+    true
+  }
+
+  /// Right after a yank, swaps the yanked text for the next older kill. Undo brings back the previous one.
+  pub fn yank_pop(&mut self) -> bool {
+    let Some(Last::Yank { start, index }) = self.last else {
+      return false;
+    };
+    self.record(None);
+    let index = (index + 1) % self.kill_ring.len();
+    let killed = self.kill_ring[index].clone();
+    self.text.replace_range(start..self.cursor, &killed);
+    self.cursor = start + killed.len();
+    self.last = Some(Last::Yank { start, index });
+    true
+  }
+
+  pub fn undo(&mut self) -> bool {
+    let Some(snapshot) = self.undo.pop() else {
+      return false;
+    };
+    let current = self.restore(snapshot);
+    self.redo.push(current);
+    true
+  }
+
+  pub fn redo(&mut self) -> bool {
+    let Some(snapshot) = self.redo.pop() else {
+      return false;
+    };
+    let current = self.restore(snapshot);
+    self.undo.push(current);
+    true
+  }
+
+  fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+    self.last = None;
+    let current = self.snapshot();
+    self.text = snapshot.text;
+    self.cursor = snapshot.cursor;
+    self.anchor = snapshot.anchor;
+    current
+  }
+
+  fn snapshot(&self) -> Snapshot {
+    Snapshot {
+      text: self.text.clone(),
+      cursor: self.cursor,
+      anchor: self.anchor,
+    }
+  }
+
+  /// Saves the state before an edit, unless it continues an edit of the same `kind`.
+  fn record(&mut self, kind: Option<Last>) {
+    if kind.is_none() || kind != self.last {
+      self.undo.push(self.snapshot());
+      if self.undo.len() > Self::UNDO {
+        self.undo.remove(0);
+      }
+    }
+    self.redo.clear();
+    self.last = kind;
+  }
+
+  fn prev_boundary(&self, position: usize) -> usize {
+    self.text[..position].grapheme_indices(true).next_back().map_or(0, |(index, _)| index)
+  }
+
+  fn next_boundary(&self, position: usize) -> usize {
+    self.text[position..]
+      .graphemes(true)
+      .next()
+      .map_or(position, |grapheme| position + grapheme.len())
+  }
+
+  /// Start of the word before `position`, skipping separators first.
+  fn prev_word(&self, position: usize) -> usize {
+    let mut graphemes = self.text[..position].grapheme_indices(true).rev().peekable();
+    while graphemes.next_if(|(_, grapheme)| !is_word(grapheme)).is_some() {}
+    let mut start = graphemes.peek().map_or(0, |(index, grapheme)| index + grapheme.len());
+    while let Some((index, _)) = graphemes.next_if(|(_, grapheme)| is_word(grapheme)) {
+      start = index;
+    }
+    start
+  }
+
+  /// End of the word after `position`, skipping separators first.
+  fn next_word(&self, position: usize) -> usize {
+    let mut graphemes = self.text[position..].grapheme_indices(true).peekable();
+    while graphemes.next_if(|(_, grapheme)| !is_word(grapheme)).is_some() {}
+    while graphemes.next_if(|(_, grapheme)| is_word(grapheme)).is_some() {}
+    graphemes.peek().map_or(self.text.len(), |(index, _)| position + index)
+  }
+}
+
+/// Paths and bucket names stop at `/`, `.` and `-`, like in a shell.
+fn is_word(grapheme: &str) -> bool {
+  grapheme.chars().next().is_some_and(|chr| chr.is_alphanumeric() || chr == '_')
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_graphemes_and_words() {
+    let mut buffer = TextBuffer::default();
+    buffer.insert("my-bucket/👩‍👩‍👧e\u{301}");
+    buffer.backspace();
+    assert_eq!(buffer.text(), "my-bucket/👩‍👩‍👧");
+    buffer.left(false);
+    assert_eq!(buffer.cursor(), "my-bucket/".len());
+
+    buffer.word_left(false);
+    assert_eq!(buffer.cursor(), "my-".len());
+    buffer.word_right(true);
+    assert_eq!(buffer.selection(), Some(3..9));
+    buffer.insert("crate");
+    assert_eq!(buffer.text(), "my-crate/👩‍👩‍👧");
+
+    buffer.undo();
+    assert_eq!(buffer.text(), "my-bucket/👩‍👩‍👧");
+    buffer.redo();
+    assert_eq!(buffer.text(), "my-crate/👩‍👩‍👧");
+  }
+
+  #[test]
+  fn test_kill_ring() {
+    let mut buffer = TextBuffer::default();
+    buffer.insert("one two three");
+    buffer.kill_word_backward();
+    buffer.kill_word_backward();
+    assert_eq!(buffer.text(), "one ");
+    buffer.home(false);
+    buffer.kill_to_end();
+
+    buffer.yank();
+    assert_eq!(buffer.text(), "one ");
+    buffer.yank_pop();
+    assert_eq!(buffer.text(), "two three");
+
+    buffer.undo();
+    assert_eq!(buffer.text(), "one ");
+    buffer.undo();
+    assert!(buffer.is_empty());
+
+    // A yank between typing is a step of its own.
+    buffer.insert("a");
+    buffer.yank();
+    buffer.insert("b");
+    assert_eq!(buffer.text(), "aone b");
+    buffer.undo();
+    assert_eq!(buffer.text(), "aone ");
+    buffer.undo();
+    assert_eq!(buffer.text(), "a");
+  }
+}
@@ -0,0 +1,115 @@
+use std::fmt::Debug;
+use std::path::{MAIN_SEPARATOR, PathBuf};
+use std::{env, fs};
+
+use crate::app::handler::Region;
+
+/// Suggests whole replacement values for what has been typed so far.
+pub trait Completer: Debug {
+  /// Candidates starting with `value`, best first.
+  fn complete(&mut self, value: &str) -> Vec<String>;
+}
+
+/// File system paths, directories end with a separator so completion can continue inside them.
+#[derive(Debug, Default, Clone)]
+pub struct PathCompleter {
+  /// Names of the last directory read and whether each is a directory, reused while typing inside it.
+  listing: Option<(PathBuf, Vec<(String, bool)>)>,
+}
+
+impl PathCompleter {
+  /// `~/` is expanded for reading but kept as typed in the candidates.
+  fn dir_to_read(typed_dir: &str) -> Option<PathBuf> {
+    match typed_dir.strip_prefix('~') {
+      Some(rest) => Some(env::home_dir()?.join(rest.trim_start_matches(MAIN_SEPARATOR))),
+      None if typed_dir.is_empty() => Some(PathBuf::from(".")),
+      None => Some(PathBuf::from(typed_dir)),
+    }
+  }
+
+  fn listing(&mut self, dir: PathBuf) -> &[(String, bool)] {
+    if self.listing.as_ref().is_none_or(|(read, _)| *read != dir) {
+      let names = fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+          // Symlinks are followed so a link to a directory still completes into it.
+          let is_dir = match entry.file_type().ok()? {
+            kind if kind.is_symlink() => entry.path().is_dir(),
+            kind => kind.is_dir(),
+          };
+          Some((entry.file_name().into_string().ok()?, is_dir))
+        })
+        .collect();
+      self.listing = Some((dir, names));
+    }
+    self.listing.as_ref().map(|(_, names)| names.as_slice()).unwrap_or_default()
+  }
+}
+
+impl Completer for PathCompleter {
+  fn complete(&mut self, value: &str) -> Vec<String> {
+    let (typed_dir, prefix) = match value.rfind(MAIN_SEPARATOR) {
+      Some(index) => value.split_at(index + 1),
+      None => ("", value),
+    };
+    let Some(dir) = Self::dir_to_read(typed_dir) else {
+      return Vec::new();
+    };
+
+    let mut candidates = self
+      .listing(dir)
+      .iter()
+      // Hidden entries only once the dot has been typed.
+      .filter(|(name, _)| name.starts_with(prefix) && (!name.starts_with('.') || prefix.starts_with('.')))
+      .map(|(name, is_dir)| {
+        let separator = if *is_dir { MAIN_SEPARATOR.to_string() } else { String::new() };
+        format!("{typed_dir}{name}{separator}")
+      })
+      .collect::<Vec<_>>();
+    candidates.sort();
+    candidates
+  }
+}
+
+/// AWS region names, matched case-insensitively.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct RegionCompleter;
+
+impl Completer for RegionCompleter {
+  fn complete(&mut self, value: &str) -> Vec<String> {
+    let value = value.trim().to_lowercase();
+    let mut candidates = <Region as strum::VariantArray>::VARIANTS
+      .iter()
+      .map(|region| region.as_ref().to_lowercase())
+      .filter(|name| name.starts_with(&value))
+      .collect::<Vec<_>>();
+    candidates.sort();
+    candidates
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_completers() {
+    let dir = env::temp_dir().join(format!("s3-creator-complete-{}", std::process::id()));
+    _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("results")).unwrap();
+    fs::write(dir.join("report.json"), "").unwrap();
+    fs::write(dir.join(".hidden"), "").unwrap();
+
+    let typed = format!("{}{MAIN_SEPARATOR}re", dir.display());
+    let mut completer = PathCompleter::default();
+    let candidates = completer.complete(&typed);
+    assert_eq!(candidates, [format!("{typed}port.json"), format!("{typed}sults{MAIN_SEPARATOR}")]);
+    assert!(completer.complete(&format!("{}{MAIN_SEPARATOR}", dir.display())).len() == 2);
+    assert!(completer.complete(&format!("{}{MAIN_SEPARATOR}.", dir.display())).len() == 1);
+
+    assert_eq!(RegionCompleter.complete("EU-CENTRAL"), ["eu-central-1", "eu-central-2"]);
+    fs::remove_dir_all(dir).unwrap();
+  }
+}
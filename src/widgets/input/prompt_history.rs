@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+/// Previously submitted values of one prompt, oldest first, kept across sessions.
+#[derive(Debug, Clone, Default)]
+pub struct PromptHistory {
+  path: Option<PathBuf>,
+  entries: Vec<String>,
+}
+
+impl PromptHistory {
+  const MAX: usize = 200;
+
+  /// Loads the history stored under `key`, a missing or unreadable file starts empty.
+  pub fn load(key: &str) -> Self {
+    let path = Self::dir().map(|dir| dir.join(key));
+    let entries = path
+      .as_ref()
+      .and_then(|path| fs::read_to_string(path).ok())
+      .map(|content| content.lines().filter(|line| !line.is_empty()).map(String::from).collect())
+      .unwrap_or_default();
+    Self { path, entries }
+  }
+
+  /// `$XDG_STATE_HOME/s3-creator/history`, `~/.local/state` when unset.
+  fn dir() -> Option<PathBuf> {
+    let state = env::var_os("XDG_STATE_HOME")
+      .filter(|dir| !dir.is_empty())
+      .map(PathBuf::from)
+      .or_else(|| env::home_dir().map(|home| home.join(".local").join("state")))?;
+    Some(state.join("s3-creator").join("history"))
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  pub fn get(&self, index: usize) -> Option<&str> {
+    self.entries.get(index).map(String::as_str)
+  }
+
+  /// Moves `value` to the most recent position and writes the file.
+  pub fn push(&mut self, value: &str) -> io::Result<()> {
+    let value = value.trim();
+    if value.is_empty() || value.contains('\n') {
+      return Ok(());
+    }
+    self.entries.retain(|entry| entry != value);
+    self.entries.push(value.to_string());
+    let overflow = self.entries.len().saturating_sub(Self::MAX);
+    self.entries.drain(..overflow);
+    self.save()
+  }
+
+  fn save(&self) -> io::Result<()> {
+    let Some(path) = &self.path else {
+      return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+      fs::create_dir_all(dir)?;
+    }
+    fs::write(path, self.entries.join("\n") + "\n")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_push() {
+    let mut history = PromptHistory::default();
+    for value in ["a", "b", "", "a"] {
+      history.push(value).unwrap();
+    }
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1), Some("a"));
+
+    for index in 0..PromptHistory::MAX {
+      history.push(&index.to_string()).unwrap();
+    }
+    assert_eq!(history.len(), PromptHistory::MAX);
+    assert_eq!(history.get(0), Some("0"));
+  }
+}
@@ -0,0 +1,136 @@
+use std::fmt::Debug;
+use std::io;
+use std::path::Path;
+
+use futures::FutureExt;
+use futures::future::BoxFuture;
+
+use crate::app::handler::Region;
+use crate::widgets::Filter;
+
+/// `Err` carries the message shown under the field.
+pub type Verdict = Result<(), String>;
+
+/// Checks a value before it can be submitted, free to touch the file system or the network.
+pub trait Validator: Debug {
+  /// Owns what it needs from `value` so the check can run on its own task.
+  fn validate(&self, value: &str) -> BoxFuture<'static, Verdict>;
+}
+
+/// A verdict on its way for the text an `Input` had when handing it out, see `Input::validation`.
+pub struct Check {
+  pub ticket: u64,
+  pub verdict: BoxFuture<'static, Verdict>,
+}
+
+/// An existing regular file that can be opened for reading.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ReadableFile;
+
+impl Validator for ReadableFile {
+  fn validate(&self, value: &str) -> BoxFuture<'static, Verdict> {
+    let value = value.trim().to_string();
+    async move {
+      let path = Path::new(&value);
+      match tokio::fs::metadata(path).await {
+        _ if value.is_empty() => Err(String::from("Enter a file path.")),
+        Err(_) => Err(String::from("File does not exist.")),
+        Ok(metadata) if !metadata.is_file() => Err(String::from("Not a file.")),
+        Ok(_) => tokio::fs::File::open(path).await.map(drop).map_err(|err| format!("Not readable: {err}.")),
+      }
+    }
+    .boxed()
+  }
+}
+
+/// A file that can be created or overwritten: its directory exists and lets us write into it.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct WritableFile;
+
+impl WritableFile {
+  /// Permission bits miss ACLs and read-only mounts, creating a file answers for both.
+  async fn can_create_in(dir: &Path) -> io::Result<()> {
+    let probe = dir.join(format!(".s3-creator-probe-{}", std::process::id()));
+    tokio::fs::OpenOptions::new().write(true).create_new(true).open(&probe).await?;
+    tokio::fs::remove_file(&probe).await
+  }
+}
+
+impl Validator for WritableFile {
+  fn validate(&self, value: &str) -> BoxFuture<'static, Verdict> {
+    let value = value.trim().to_string();
+    async move {
+      let path = Path::new(&value);
+      if value.is_empty() {
+        return Err(String::from("Enter a file path."));
+      }
+      if tokio::fs::metadata(path).await.is_ok_and(|metadata| metadata.is_dir()) {
+        return Err(String::from("This is a directory."));
+      }
+
+      let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+      match tokio::fs::metadata(dir).await {
+        Err(_) => Err(format!("Directory {} does not exist.", dir.display())),
+        Ok(metadata) if !metadata.is_dir() => Err(format!("{} is not a directory.", dir.display())),
+        Ok(_) if tokio::fs::metadata(path).await.is_ok() => {
+          // Overwriting only needs the file itself to be writable.
+          let opened = tokio::fs::OpenOptions::new().write(true).open(path).await;
+          opened.map(drop).map_err(|err| format!("Not writable: {err}."))
+        }
+        Ok(_) => Self::can_create_in(dir)
+          .await
+          .map_err(|err| format!("Cannot write in {}: {err}.", dir.display())),
+      }
+    }
+    .boxed()
+  }
+}
+
+/// One of the known AWS region names.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ValidRegion;
+
+impl Validator for ValidRegion {
+  fn validate(&self, value: &str) -> BoxFuture<'static, Verdict> {
+    let verdict = match Region::from_name(value) {
+      Some(_) => Ok(()),
+      None => Err(format!("Unknown region {:?}.", value.trim())),
+    };
+    async move { verdict }.boxed()
+  }
+}
+
+/// A logs search, only `re:` queries can fail to compile.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SearchQuery;
+
+impl Validator for SearchQuery {
+  fn validate(&self, value: &str) -> BoxFuture<'static, Verdict> {
+    let verdict = Filter::default().search(value).map_err(|err| err.to_string());
+    async move { verdict }.boxed()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::env;
+
+  use super::*;
+
+  #[tokio::test]
+  async fn test_validators() {
+    let dir = env::temp_dir();
+    let missing = dir.join("s3-creator-missing").join("out.json");
+
+    assert!(ReadableFile.validate(&dir.display().to_string()).await.is_err());
+    assert!(WritableFile.validate(&dir.join("out.json").display().to_string()).await.is_ok());
+    assert!(WritableFile.validate(&missing.display().to_string()).await.is_err());
+    #[cfg(unix)]
+    assert!(WritableFile.validate("/proc/out.json").await.is_err());
+
+    assert!(ValidRegion.validate(" EU-West-1 ").await.is_ok());
+    assert!(ValidRegion.validate("eu-west-9").await.is_err());
+    assert!(SearchQuery.validate("re:[").await.is_err());
+    assert!(SearchQuery.validate("[").await.is_ok());
+  }
+}
@@ -4,9 +4,18 @@ use anyhow::Error;
 use ratatui::prelude::{Color, Stylize};
 use ratatui::text::{Line, Span, ToSpan};
 use ratatui::widgets::ListItem;
+use strum::{Display, VariantArray};
 
 use crate::app::handler::BucketStatus;
-use crate::widgets::Timestamp;
+use crate::widgets::{Details, Timestamp};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Display, VariantArray)]
+pub enum Level {
+  Bucket,
+  Info,
+  Warn,
+  Error,
+}
 
 #[derive(Debug)]
 pub enum Log {
@@ -21,6 +30,65 @@ impl Log {
     self.into()
   }
 
+  pub fn as_line(&self) -> Line<'_> {
+    self.with_timestamp(self.content())
+  }
+
+  /// The line without its timestamp, what searches look at.
+  pub fn content(&self) -> Line<'_> {
+    match self {
+      Log::Bucket(bucket) => bucket.content_line(),
+      Log::Info { line, .. } | Log::Warn { line, .. } => Line::from(Span::from(line.as_ref()).fg(self.color_content())),
+      Log::Error { error, .. } => Line::from(error.to_span().fg(self.color_content())),
+    }
+  }
+
+  pub fn with_timestamp<'a>(&'a self, content: Line<'a>) -> Line<'a> {
+    let mut line = Line::from_iter(self.timestamp_span());
+    line.spans.extend(content.spans);
+    line
+  }
+
+  /// One line for the log file: full timestamp, level, then the whole error chain.
+  pub fn record(&self) -> String {
+    let level = self.level().to_string().to_uppercase();
+    format!("{} {level:<6} {}", self.timestamp().to_rfc3339(), self.message())
+  }
+
+  /// Bucket entries copy as the bare name, the rest as their message.
+  pub fn copy_text(&self) -> String {
+    match self {
+      Log::Bucket(bucket) => bucket.name().to_string(),
+      log => log.message(),
+    }
+  }
+
+  /// Plain single-line text, without timestamp or colors.
+  pub fn message(&self) -> String {
+    let message = match self {
+      Log::Bucket(bucket) => bucket.to_string(),
+      Log::Info { line, .. } | Log::Warn { line, .. } => line.to_string(),
+      Log::Error { error, .. } => format!("{error:#}"),
+    };
+    message.replace('\n', " ")
+  }
+
+  pub fn timestamp(&self) -> Timestamp {
+    match *self {
+      Log::Bucket(ref bucket) => bucket.timestamp(),
+      Log::Info { timestamp, .. } | Log::Error { timestamp, .. } | Log::Warn { timestamp, .. } => timestamp,
+    }
+  }
+
+  pub fn level(&self) -> Level {
+    match self {
+      Log::Bucket(_) => Level::Bucket,
+      Log::Info { .. } => Level::Info,
+      Log::Warn { .. } => Level::Warn,
+      Log::Error { .. } => Level::Error,
+    }
+  }
+
   pub fn bucket(bucket: BucketStatus) -> Self {
     Self::Bucket(bucket)
   }
@@ -43,7 +111,7 @@ impl Log {
     }
   }
 
-  fn timestamp_span(&self) -> [Span; 3] {
+  fn timestamp_span(&self) -> [Span<'_>; 3] {
     match *self {
       Log::Bucket(ref bucket) => bucket.timestamp().as_spans(),
       Log::Info { ref timestamp, .. } | Log::Error { ref timestamp, .. } | Log::Warn { ref timestamp, .. } => timestamp.as_spans(),
@@ -62,17 +130,39 @@ impl Log {
 
 impl<'a> From<&'a Log> for ListItem<'a> {
   fn from(log: &'a Log) -> Self {
-    let mut spans = log.timestamp_span().to_vec();
+    ListItem::from(log.as_line())
+  }
+}
 
-    let content = match log {
-      Log::Bucket(bucket) => return ListItem::from(bucket),
-      Log::Info { line, .. } => Span::from(line.as_ref()).fg(log.color_content()),
-      Log::Warn { line, .. } => Span::from(line.as_ref()).fg(log.color_content()),
-      Log::Error { error, .. } => error.to_span().fg(log.color_content()),
-    };
+impl From<&Log> for Details {
+  fn from(log: &Log) -> Self {
+    match log {
+      Log::Bucket(bucket) => Details::from(bucket),
+      Log::Info { timestamp, line } | Log::Warn { timestamp, line } => Details::new(log.level().to_string())
+        .field("Timestamp", timestamp.to_rfc3339())
+        .field("Message", line.to_string()),
+      Log::Error { timestamp, error } => error.chain().skip(1).fold(
+        Details::new("Error")
+          .field("Timestamp", timestamp.to_rfc3339())
+          .field("Error", error.to_string()),
+        |details, cause| details.field("Caused by", cause.to_string()),
+      ),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
 
-    spans.push(content);
+  #[test]
+  fn test_error_details() {
+    let error = anyhow::anyhow!("connection reset").context("HEAD bucket failed");
+    let details = Details::from(&Log::error(error));
+    let lines = details.lines().iter().map(ToString::to_string).collect::<Vec<_>>();
 
-    ListItem::from(Line::from_iter(spans))
+    assert_eq!(lines.len(), 3);
+    assert!(lines[1].ends_with("HEAD bucket failed"));
+    assert!(lines[2].starts_with("Caused by") && lines[2].ends_with("connection reset"));
   }
 }
@@ -1,62 +1,246 @@
 use std::collections::VecDeque;
-use std::ops::DerefMut;
+use std::ops::Range;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crossterm::event::{KeyEvent, MouseEvent, MouseEventKind};
 use helper::{UnhandledEvent, keys};
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Position, Rect};
-use ratatui::prelude::{StatefulWidget, Widget};
-use ratatui::text::Line;
-use ratatui::widgets::{List, ListDirection, ListState};
+use ratatui::prelude::{StatefulWidget, Stylize, Widget};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListDirection, ListItem, ListState};
+use strum::VariantArray;
 use tokio::sync::RwLock;
 
+use crate::app::Export;
 use crate::areas::KnownArea;
+use crate::config::Action;
 use crate::mouse_area;
-use crate::ui::{blk, clear};
-use crate::widgets::Log;
+use crate::ui::{blk, clear, colors};
+use crate::widgets::{Details, Level, Log};
 
-#[derive(Debug, Default)]
+mod filter;
+pub use filter::*;
+
+mod sink;
+pub use sink::*;
+
+#[derive(Debug)]
 pub struct Logs {
-  items: Arc<RwLock<VecDeque<Log>>>,
-  state: Arc<RwLock<ListState>>,
+  items: Arc<RwLock<Entries>>,
+  capacity: Arc<AtomicUsize>,
+  sink: Sink,
+  state: Arc<RwLock<Selection>>,
+  filter: Arc<RwLock<Filter>>,
   known_area: KnownArea,
 }
 
+/// Entries numbered by arrival, the oldest go once there are more than the capacity.
+#[derive(Debug, Default)]
+struct Entries {
+  logs: VecDeque<Log>,
+  /// Arrival number of `logs[0]`.
+  first: usize,
+}
+
+impl Entries {
+  fn push(&mut self, log: Log, capacity: usize) {
+    self.logs.push_back(log);
+    self.truncate(capacity);
+  }
+
+  fn truncate(&mut self, capacity: usize) {
+    while self.logs.len() > capacity {
+      self.logs.pop_front();
+      self.first += 1;
+    }
+  }
+
+  /// What `filter` lets through with arrival numbers, newest first as shown.
+  fn shown<'a>(&'a self, filter: &'a Filter) -> impl Iterator<Item = (usize, &'a Log)> + 'a {
+    let first = self.first;
+    self
+      .logs
+      .iter()
+      .enumerate()
+      .rev()
+      .map(move |(index, log)| (first + index, log))
+      .filter(|(_, log)| filter.matches(log))
+  }
+}
+
+/// The list position and the arrival number of the selected entry, which stays put while new ones stream in.
+#[derive(Debug, Default)]
+struct Selection {
+  list: ListState,
+  anchor: Option<usize>,
+}
+
+impl Default for Logs {
+  fn default() -> Self {
+    Self::new(LogConfig::default())
+  }
+}
+
 impl Logs {
+  /// A log file that can't be opened or written is reported as an entry instead of failing.
+  pub fn new(config: LogConfig) -> Self {
+    let items = Arc::new(RwLock::new(Entries::default()));
+    let capacity = Arc::new(AtomicUsize::new(config.capacity));
+    let sink = Sink::spawn(&config, {
+      let (items, capacity) = (Arc::clone(&items), Arc::clone(&capacity));
+      move |err| {
+        let log = Log::error(anyhow::anyhow!(err));
+        items.blocking_write().push(log, capacity.load(Ordering::Relaxed))
+      }
+    });
+
+    Self {
+      items,
+      capacity,
+      sink,
+      state: Default::default(),
+      filter: Default::default(),
+      known_area: Default::default(),
+    }
+  }
+
+  /// Takes a reloaded configuration, the log file is reopened and extra entries are dropped.
+  pub async fn reconfigure(&self, config: &LogConfig) {
+    self.sink.reopen(config);
+    self.capacity.store(config.capacity, Ordering::Relaxed);
+
+    self.items.write().await.truncate(config.capacity);
+  }
+
+  /// Actions of the logs context, see `Keymap`.
+  pub async fn perform(&self, action: Action) -> Option<UnhandledEvent> {
+    match action {
+      Action::ScrollUp => self.navigate(|list, _| list.scroll_up_by(1)).await,
+      Action::ScrollDown => self.navigate(|list, _| list.scroll_down_by(1)).await,
+      Action::PageUp => self.navigate(|list, _| list.scroll_up_by(10)).await,
+      Action::PageDown => self.navigate(|list, _| list.scroll_down_by(10)).await,
+      Action::Top => self.navigate(|list, count| list.select(count.checked_sub(1))).await,
+      Action::Bottom => self.navigate(|list, _| list.select(Some(0))).await,
+      Action::NextMatch => self.next_match(true).await,
+      Action::PreviousMatch => self.next_match(false).await,
+      _ => return None,
+    }
+
+    Some(UnhandledEvent::render())
+  }
+
+  /// Digits pick a level by position, they aren't rebindable.
   pub async fn handle_key(&self, key: KeyEvent) -> Option<UnhandledEvent> {
     match key {
-      keys!(Up, NONE, Press) => {
-        self.state.write().await.scroll_up_by(1);
-        Some(UnhandledEvent::render())
-      }
-      keys!(Down, NONE, Press) => {
-        self.state.write().await.scroll_down_by(1);
-        Some(UnhandledEvent::render())
-      }
-      keys!(PageUp, NONE, Press) => {
-        self.state.write().await.scroll_up_by(10);
-        Some(UnhandledEvent::render())
-      }
-      keys!(PageDown, NONE, Press) => {
-        self.state.write().await.scroll_down_by(10);
-        Some(UnhandledEvent::render())
-      }
-      keys!(Home, NONE, Press) => {
-        let items_count = self.items.read().await.len();
-        if items_count > 0 {
-          self.state.write().await.select(Some(items_count - 1));
-        }
-        Some(UnhandledEvent::render())
-      }
-      keys!(End, NONE, Press) => {
-        self.state.write().await.select(Some(0));
+      keys!(Char(digit), NONE, Press) => {
+        let level = Self::level_key(digit)?;
+        let mut filter = self.filter.write().await;
+        filter.level = if filter.level == level { None } else { level };
+        *self.state.write().await = Selection::default();
         Some(UnhandledEvent::render())
       }
       _ => None,
     }
   }
 
+  /// `0` for every level, then each level by position.
+  fn level_key(digit: char) -> Option<Option<Level>> {
+    match digit.to_digit(10)? {
+      0 => Some(None),
+      index => Level::VARIANTS.get(index as usize - 1).copied().map(Some),
+    }
+  }
+
+  /// The digits `level_key` takes.
+  fn level_digits() -> String {
+    format!("0-{}", Level::VARIANTS.len())
+  }
+
+  /// What `handle_key` does, for the help.
+  pub fn fixed_keys() -> Vec<(String, &'static str)> {
+    vec![(Self::level_digits(), "Filter by level, 0 shows all")]
+  }
+
+  /// An invalid regex keeps the previous search.
+  pub async fn search(&self, query: &str) -> Result<(), regex::Error> {
+    self.filter.write().await.search(query)?;
+    *self.state.write().await = Selection::default();
+    Ok(())
+  }
+
+  async fn with_selected<T>(&self, f: impl FnOnce(&Log) -> T) -> Option<T> {
+    let filter = self.filter.read().await;
+    let items = self.items.read().await;
+    let anchor = self.state.read().await.anchor?;
+    items.shown(&filter).find(|(arrival, _)| *arrival == anchor).map(|(_, log)| f(log))
+  }
+
+  /// Moves the selection among the shown entries, `f` gets their count, then anchors it to the entry it lands on.
+  async fn navigate(&self, f: impl FnOnce(&mut ListState, usize)) {
+    let filter = self.filter.read().await;
+    let arrivals = self.items.read().await.shown(&filter).map(|(arrival, _)| arrival).collect::<Vec<_>>();
+    let mut state = self.state.write().await;
+    let Selection { list, anchor } = &mut *state;
+
+    list.select(anchor.and_then(|anchor| arrivals.iter().position(|arrival| *arrival == anchor)));
+    f(list, arrivals.len());
+    let index = list
+      .selected()
+      .filter(|_| !arrivals.is_empty())
+      .map(|index| index.min(arrivals.len() - 1));
+    list.select(index);
+    *anchor = index.map(|index| arrivals[index]);
+  }
+
+  pub async fn details(&self) -> Option<Details> {
+    self.with_selected(|log| Details::from(log)).await
+  }
+
+  pub async fn selected_text(&self) -> Option<String> {
+    self.with_selected(Log::copy_text).await
+  }
+
+  /// The filtered view, oldest first.
+  pub async fn export(&self) -> Export {
+    let filter = self.filter.read().await;
+    let rows = self
+      .items
+      .read()
+      .await
+      .logs
+      .iter()
+      .filter(|log| filter.matches(log))
+      .map(|log| vec![log.timestamp().to_rfc3339(), log.level().to_string(), log.message()])
+      .collect();
+
+    Export {
+      columns: ["Timestamp", "Level", "Message"].map(String::from).to_vec(),
+      rows,
+    }
+  }
+
+  pub async fn query(&self) -> String {
+    self.filter.read().await.query().to_string()
+  }
+
+  /// Every shown item matches, so this walks them with wrap-around; forward goes to older entries.
+  async fn next_match(&self, forward: bool) {
+    self
+      .navigate(|list, count| {
+        let next = match list.selected() {
+          _ if count == 0 => None,
+          None => Some(0),
+          Some(index) if forward => Some((index + 1) % count),
+          Some(index) => Some((index + count - 1) % count),
+        };
+        list.select(next);
+      })
+      .await
+  }
+
   pub async fn handle_mouse(&self, mouse_event: MouseEvent) -> Option<UnhandledEvent> {
     use MouseEventKind::{ScrollDown, ScrollUp};
 
@@ -67,8 +251,8 @@ impl Logs {
     }
 
     match mouse_event.kind {
-      ScrollUp => self.state.write().await.scroll_up_by(1),
-      ScrollDown => self.state.write().await.scroll_down_by(1),
+      ScrollUp => self.navigate(|list, _| list.scroll_up_by(1)).await,
+      ScrollDown => self.navigate(|list, _| list.scroll_down_by(1)).await,
       _ => return None,
     }
 
@@ -80,11 +264,8 @@ impl Logs {
   }
 
   pub async fn add(&self, log: Log) {
-    let mut items = self.items.write().await;
-    items.push_back(log);
-    if items.len() > 5000 {
-      items.pop_front();
-    }
+    self.sink.write(log.record());
+    self.items.write().await.push(log, self.capacity.load(Ordering::Relaxed));
   }
 }
 
@@ -92,26 +273,121 @@ impl Clone for Logs {
   fn clone(&self) -> Self {
     Self {
       items: Arc::clone(&self.items),
+      capacity: Arc::clone(&self.capacity),
+      sink: self.sink.clone(),
       state: Default::default(),
+      filter: Default::default(),
       known_area: Default::default(),
     }
   }
 }
 
-impl Widget for &Logs {
-  fn render(self, area: Rect, buf: &mut Buffer) {
+impl Logs {
+  pub fn render_focused(&self, area: Rect, buf: &mut Buffer, focused: bool) {
     self.known_area.replace(area);
     clear(area, buf);
 
-    let title = Line::raw(" 📈Activities ").left_aligned();
-    let block = blk().title_top(title);
+    let filter = self.filter.blocking_read();
+    let mut title = String::from(" 📈Activities ");
+    if let Some(level) = filter.level {
+      title.push_str(&format!("[{level}] "));
+    }
+    if !filter.query().is_empty() {
+      title.push_str(&format!("/{}/ ", filter.query()));
+    }
+    let controls = Line::from(vec![
+      Span::raw(" "),
+      Span::raw(format!("[{}]", Self::level_digits())).blue(),
+      Span::raw(" Level "),
+      Span::raw("[/]").blue(),
+      Span::raw(" Search "),
+      Span::raw("[n/N]").blue(),
+      Span::raw(" Match "),
+      Span::raw("[ENTER]").blue(),
+      Span::raw(" Details "),
+    ])
+    .right_aligned();
+    let mut block = blk().title_top(Line::raw(title).left_aligned()).title_bottom(controls);
+    if focused {
+      block = block.border_style(colors().focus);
+    }
     let locked_items = self.items.blocking_read();
-    let items = locked_items.iter().map(Log::as_list_item).rev().collect::<Vec<_>>();
-    let list = List::default().items(items).block(block).direction(ListDirection::BottomToTop);
+    let mut state = self.state.blocking_write();
+    let shown = locked_items.shown(&filter).collect::<Vec<_>>();
+    let selected = state.anchor.and_then(|anchor| shown.iter().position(|(arrival, _)| *arrival == anchor));
+    state.list.select(selected);
+
+    // Only the entries in view are built and highlighted, the pane can hold the whole memory cap.
+    let (offset, window) = Self::window(&mut state.list, shown.len(), area.height.saturating_sub(2) as usize);
+    let items = shown[window]
+      .iter()
+      .map(|(_, log)| ListItem::new(log.with_timestamp(filter.highlight(log.content()))));
+    let list = List::default()
+      .items(items)
+      .block(block)
+      .direction(ListDirection::BottomToTop)
+      .highlight_style(Style::new().reversed());
 
-    {
-      let mut state = self.state.blocking_write();
-      StatefulWidget::render(list, area, buf, state.deref_mut())
+    let mut visible = ListState::default().with_selected(selected.map(|selected| selected - offset));
+    StatefulWidget::render(list, area, buf, &mut visible);
+  }
+
+  /// Scrolls `state` just enough to keep the selection in view, like `Results::window`, and returns the offset and the
+  /// entries to draw.
+  fn window(state: &mut ListState, len: usize, height: usize) -> (usize, Range<usize>) {
+    let height = height.max(1);
+    let mut offset = state.offset().min(len.saturating_sub(height));
+    match state.selected() {
+      Some(selected) if selected < offset => offset = selected,
+      Some(selected) if selected >= offset + height => offset = selected + 1 - height,
+      _ => {}
     }
+    *state.offset_mut() = offset;
+    (offset, offset..(offset + height).min(len))
+  }
+}
+
+impl Widget for &Logs {
+  fn render(self, area: Rect, buf: &mut Buffer) {
+    self.render_focused(area, buf, false);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_window() {
+    let mut state = ListState::default();
+    assert_eq!(Logs::window(&mut state, 100, 10), (0, 0..10));
+    state.select(Some(42));
+    assert_eq!(Logs::window(&mut state, 100, 10), (33, 33..43));
+    state.select(Some(35));
+    assert_eq!(Logs::window(&mut state, 100, 10), (33, 33..43));
+    state.select(None);
+    assert_eq!(Logs::window(&mut state, 5, 10), (0, 0..5));
+  }
+
+  #[tokio::test]
+  async fn test_anchored_selection() {
+    let logs = Logs::new(LogConfig {
+      capacity: 3,
+      ..Default::default()
+    });
+    logs.add(Log::info("first")).await;
+    logs.add(Log::info("second")).await;
+    logs.perform(Action::Bottom).await;
+    assert_eq!(logs.selected_text().await.as_deref(), Some("second"));
+
+    logs.add(Log::info("third")).await;
+    logs.add(Log::info("fourth")).await;
+    assert_eq!(logs.selected_text().await.as_deref(), Some("second"));
+    logs.perform(Action::ScrollUp).await;
+    assert_eq!(logs.selected_text().await.as_deref(), Some("third"));
+
+    logs.add(Log::info("fifth")).await;
+    logs.add(Log::info("sixth")).await;
+    assert_eq!(logs.selected_text().await, None);
   }
 }
@@ -0,0 +1,181 @@
+use std::ops::Range;
+
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use regex::{Regex, RegexBuilder};
+
+use crate::widgets::{Level, Log};
+
+/// What the Activities pane shows: one level or all of them, narrowed by an optional search.
+#[derive(Debug, Default, Clone)]
+pub struct Filter {
+  pub level: Option<Level>,
+  query: String,
+  pattern: Option<Regex>,
+}
+
+impl Filter {
+  const REGEX_PREFIX: &'static str = "re:";
+  const HIGHLIGHT: Style = Style::new().fg(Color::Black).bg(Color::Yellow);
+
+  /// Plain text is matched literally, `re:` switches to a regex. Both are case-insensitive unless the query has an uppercase letter.
+  pub fn search(&mut self, query: &str) -> Result<(), regex::Error> {
+    let pattern = match query.strip_prefix(Self::REGEX_PREFIX) {
+      _ if query.is_empty() => None,
+      Some(regex) => Some(Self::compile(regex, Self::has_uppercase(regex, true))?),
+      None => Some(Self::compile(&regex::escape(query), Self::has_uppercase(query, false))?),
+    };
+    self.query = query.to_string();
+    self.pattern = pattern;
+    Ok(())
+  }
+
+  fn compile(pattern: &str, case_sensitive: bool) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).case_insensitive(!case_sensitive).build()
+  }
+
+  /// In a regex, escapes like `\S`, `\W` or `\p{Lu}` name classes rather than letters to match and don't count.
+  fn has_uppercase(query: &str, regex: bool) -> bool {
+    let mut chars = query.chars();
+    while let Some(chr) = chars.next() {
+      match chr {
+        '\\' if regex => {
+          // Code points and classes carry an argument, in braces or of a fixed width.
+          let width = match chars.next() {
+            Some('p' | 'P') => 1,
+            Some('x') => 2,
+            Some('u') => 4,
+            Some('U') => 8,
+            _ => continue,
+          };
+          if chars.clone().next() == Some('{') {
+            chars.find(|chr| *chr == '}');
+          } else {
+            chars.by_ref().take(width).for_each(drop);
+          }
+        }
+        chr if chr.is_uppercase() => return true,
+        _ => {}
+      }
+    }
+    false
+  }
+
+  pub fn query(&self) -> &str {
+    &self.query
+  }
+
+  pub fn is_active(&self) -> bool {
+    self.level.is_some() || self.pattern.is_some()
+  }
+
+  /// The search only sees the content, so `^` anchors at the start of the message.
+  pub fn matches(&self, log: &Log) -> bool {
+    if self.level.is_some_and(|level| level != log.level()) {
+      return false;
+    }
+    match self.pattern {
+      Some(ref pattern) => pattern.is_match(&text(&log.content())),
+      None => true,
+    }
+  }
+
+  /// Splits spans around every match so the original colors survive outside of it.
+  pub fn highlight<'a>(&self, line: Line<'a>) -> Line<'a> {
+    let Some(ref pattern) = self.pattern else {
+      return line;
+    };
+    let text = text(&line);
+    let ranges = pattern
+      .find_iter(&text)
+      .map(|found| found.range())
+      .filter(|range| !range.is_empty())
+      .collect::<Vec<Range<usize>>>();
+    if ranges.is_empty() {
+      return line;
+    }
+
+    let mut spans = Vec::with_capacity(line.spans.len() + ranges.len() * 2);
+    let mut offset = 0;
+    for span in line.spans {
+      let (start, end) = (offset, offset + span.content.len());
+      offset = end;
+
+      let mut cursor = start;
+      for range in ranges.iter().filter(|range| range.start < end && range.end > start) {
+        let (from, to) = (range.start.max(start), range.end.min(end));
+        if cursor < from {
+          spans.push(Span::styled(text[cursor..from].to_string(), span.style));
+        }
+        spans.push(Span::styled(text[from..to].to_string(), span.style.patch(Self::HIGHLIGHT)));
+        cursor = to;
+      }
+      if cursor < end {
+        spans.push(Span::styled(text[cursor..end].to_string(), span.style));
+      }
+    }
+
+    Line { spans, ..line }
+  }
+}
+
+fn text(line: &Line) -> String {
+  line.spans.iter().map(|span| span.content.as_ref()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_filter() {
+    let mut filter = Filter::default();
+    let (info, warn) = (Log::info("Bucket list loaded"), Log::warn("slow down"));
+    assert!(filter.matches(&info) && filter.matches(&warn));
+
+    filter.level = Some(Level::Warn);
+    assert!(!filter.matches(&info) && filter.matches(&warn));
+
+    filter.level = None;
+    filter.search("bucket").unwrap();
+    assert!(filter.matches(&info) && !filter.matches(&warn));
+    filter.search("Bucket").unwrap();
+    assert!(filter.matches(&info));
+    filter.search("bucket list").unwrap();
+    assert!(filter.matches(&info));
+    filter.search("BUCKET").unwrap();
+    assert!(!filter.matches(&info));
+
+    filter.search("re:^slo+w").unwrap();
+    assert!(!filter.matches(&info) && filter.matches(&warn));
+    filter.search("re:^bucket").unwrap();
+    assert!(filter.matches(&info) && !filter.matches(&warn));
+    // The timestamp isn't searched.
+    filter.search(":").unwrap();
+    assert!(!filter.matches(&info) && !filter.matches(&warn));
+    // Escapes are classes, not uppercase letters.
+    filter.search(r"re:\Slow\Wdown\p{Ll}*").unwrap();
+    assert!(filter.matches(&Log::warn("SLOW DOWN")));
+    filter.search(r"re:\SLOW").unwrap();
+    assert!(!filter.matches(&warn));
+    filter.search("re:slo+w").unwrap();
+    assert!(filter.matches(&warn));
+
+    assert!(filter.search("re:(").is_err());
+    assert_eq!(filter.query(), "re:slo+w");
+  }
+
+  #[test]
+  fn test_highlight() {
+    let mut filter = Filter::default();
+    filter.search("lo w").unwrap();
+    let line = Line::from(vec![Span::raw("hello"), Span::styled(" world", Color::Red)]);
+    let spans = filter.highlight(line).spans;
+
+    let parts = spans.iter().map(|span| span.content.as_ref()).collect::<Vec<_>>();
+    assert_eq!(parts, ["hel", "lo", " w", "orld"]);
+    assert_eq!(spans[1].style, Filter::HIGHLIGHT);
+    assert_eq!(spans[2].style, Style::new().fg(Color::Red).patch(Filter::HIGHLIGHT));
+    assert_eq!(spans[3].style, Style::new().fg(Color::Red));
+  }
+}
@@ -0,0 +1,209 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+
+use serde::Deserialize;
+
+/// Where the activity log goes besides the screen, the `[log]` table of the configuration file.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+  /// Mirror every entry into this file when set.
+  pub file: Option<PathBuf>,
+  /// Rotate once the current file would grow past this size.
+  pub max_bytes: u64,
+  /// Rotated files kept next to the current one, as `<file>.1` (newest) to `<file>.<max_files>`.
+  pub max_files: usize,
+  /// Entries kept in memory for the Activities pane.
+  pub capacity: usize,
+}
+
+impl Default for LogConfig {
+  fn default() -> Self {
+    Self {
+      file: None,
+      max_bytes: 10 * 1024 * 1024,
+      max_files: 5,
+      capacity: 5000,
+    }
+  }
+}
+
+enum Command {
+  Write(String),
+  Open(LogConfig),
+}
+
+/// Owns the log file on its own thread, so blocking writes never hold up the async tasks.
+#[derive(Debug, Clone)]
+pub struct Sink(Sender<Command>);
+
+impl Sink {
+  /// `failed` hears about a file that can't be opened or written, the file is dropped after a failed write.
+  pub fn spawn(config: &LogConfig, failed: impl Fn(String) + Send + 'static) -> Self {
+    let (sender, receiver) = mpsc::channel();
+    _ = sender.send(Command::Open(config.clone()));
+    thread::Builder::new()
+      .name(String::from("log-sink"))
+      .spawn(move || {
+        let mut file = None;
+        for command in receiver {
+          match command {
+            Command::Open(config) => {
+              file = Self::open(&config).unwrap_or_else(|err| {
+                failed(err);
+                None
+              })
+            }
+            Command::Write(line) => {
+              if let Some(ref mut open) = file
+                && let Err(err) = open.write_line(&line)
+              {
+                file = None;
+                failed(format!("Log file disabled after a failed write: {err}"));
+              }
+            }
+          }
+        }
+      })
+      .expect("the log sink thread can be spawned");
+    Self(sender)
+  }
+
+  fn open(config: &LogConfig) -> Result<Option<RotatingFile>, String> {
+    let Some(ref path) = config.file else {
+      return Ok(None);
+    };
+    RotatingFile::open(path, config.max_bytes, config.max_files)
+      .map(Some)
+      .map_err(|err| format!("Can't open log file {}: {err}", path.display()))
+  }
+
+  pub fn write(&self, line: String) {
+    _ = self.0.send(Command::Write(line));
+  }
+
+  /// Closes the current file and opens the one `config` names, if any.
+  pub fn reopen(&self, config: &LogConfig) {
+    _ = self.0.send(Command::Open(config.clone()));
+  }
+}
+
+/// Append-only file that shifts itself to `<file>.1` when full.
+#[derive(Debug)]
+pub struct RotatingFile {
+  path: PathBuf,
+  max_bytes: u64,
+  max_files: usize,
+  writer: BufWriter<File>,
+  written: u64,
+}
+
+impl RotatingFile {
+  pub fn open(path: impl Into<PathBuf>, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+    let path = path.into();
+    let file = Self::append(&path)?;
+    Ok(Self {
+      written: file.metadata()?.len(),
+      writer: BufWriter::new(file),
+      path,
+      max_bytes,
+      max_files,
+    })
+  }
+
+  fn append(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+      fs::create_dir_all(parent)?;
+    }
+    File::options().create(true).append(true).open(path)
+  }
+
+  fn rotated(&self, index: usize) -> PathBuf {
+    let mut path = self.path.clone().into_os_string();
+    path.push(format!(".{index}"));
+    PathBuf::from(path)
+  }
+
+  /// Flushed right away, a crash must not eat the entries explaining it.
+  pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+    let len = line.len() as u64 + 1;
+    if self.written > 0 && self.written + len > self.max_bytes {
+      self.rotate()?;
+    }
+
+    self.writer.write_all(line.as_bytes())?;
+    self.writer.write_all(b"\n")?;
+    self.writer.flush()?;
+    self.written += len;
+    Ok(())
+  }
+
+  fn rotate(&mut self) -> io::Result<()> {
+    self.writer.flush()?;
+
+    if self.max_files == 0 {
+      fs::remove_file(&self.path)?;
+    } else {
+      for index in (1..self.max_files).rev() {
+        let from = self.rotated(index);
+        if from.exists() {
+          fs::rename(from, self.rotated(index + 1))?;
+        }
+      }
+      fs::rename(&self.path, self.rotated(1))?;
+    }
+
+    self.writer = BufWriter::new(Self::append(&self.path)?);
+    self.written = 0;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::env;
+
+  use super::*;
+
+  #[test]
+  fn test_rotation() {
+    let dir = env::temp_dir().join(format!("s3-creator-log-{}", std::process::id()));
+    let path = dir.join("activity.log");
+    _ = fs::remove_dir_all(&dir);
+
+    let mut file = RotatingFile::open(&path, 10, 2).unwrap();
+    for line in ["first", "second", "third", "fourth"] {
+      file.write_line(line).unwrap();
+    }
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "fourth\n");
+    assert_eq!(fs::read_to_string(file.rotated(1)).unwrap(), "third\n");
+    assert_eq!(fs::read_to_string(file.rotated(2)).unwrap(), "second\n");
+    assert!(!file.rotated(3).exists());
+
+    // Reopening keeps appending to the current file.
+    drop(file);
+    let mut file = RotatingFile::open(&path, 100, 2).unwrap();
+    file.write_line("fifth").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "fourth\nfifth\n");
+
+    fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn test_sink_failure() {
+    let (sender, receiver) = mpsc::channel();
+    let config = LogConfig {
+      file: Some(PathBuf::from("/dev/null/activity.log")),
+      ..Default::default()
+    };
+    let sink = Sink::spawn(&config, move |err| _ = sender.send(err));
+    sink.write(String::from("dropped"));
+
+    let err = receiver.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+    assert!(err.starts_with("Can't open log file /dev/null/activity.log"));
+  }
+}
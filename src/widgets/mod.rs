@@ -4,17 +4,44 @@ pub use input::*;
 mod statistic;
 pub use statistic::*;
 
+mod breakdown;
+pub use breakdown::*;
+
+mod history;
+pub use history::*;
+
 mod logs;
 pub use logs::*;
 
+mod results;
+pub use results::*;
+
+mod details;
+pub use details::*;
+
+mod help;
+pub use help::*;
+
+mod file_picker;
+pub use file_picker::*;
+
+mod form;
+pub use form::*;
+
 mod confirmation;
 pub use confirmation::*;
 
 mod alert;
 pub use alert::*;
 
+mod notification;
+pub use notification::*;
+
 mod timestamp;
 pub use timestamp::*;
 
 mod log;
 pub use log::*;
+
+mod ticket;
+pub use ticket::*;
@@ -0,0 +1,142 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::prelude::{Stylize, Widget};
+use ratatui::style::Color;
+use ratatui::text::Line;
+use ratatui::widgets::{Paragraph, Wrap};
+use strum::Display;
+
+use crate::ui::{blk, clear};
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Display)]
+pub enum Severity {
+  Info,
+  #[default]
+  Warning,
+  Error,
+}
+
+impl Severity {
+  pub fn color(&self) -> Color {
+    match self {
+      Severity::Info => Color::Rgb(0, 251, 255),
+      Severity::Warning => Color::Rgb(255, 123, 0),
+      Severity::Error => Color::Rgb(255, 60, 60),
+    }
+  }
+
+  pub fn icon(&self) -> &'static str {
+    match self {
+      Severity::Info => "ℹ️",
+      Severity::Warning => "⚠️",
+      Severity::Error => "⛔",
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+struct Toast {
+  severity: Severity,
+  message: Arc<str>,
+  expires: Instant,
+}
+
+/// Non-modal notifications stacked in the top right corner, shared with the background tasks.
+#[derive(Debug, Default, Clone)]
+pub struct Toasts {
+  items: Arc<Mutex<VecDeque<Toast>>>,
+}
+
+impl Toasts {
+  pub const TIMEOUT: Duration = Duration::from_secs(5);
+  const WIDTH: u16 = 44;
+  const HEIGHT: u16 = 3;
+  const MAX: usize = 4;
+
+  /// A message already on screen gets its timeout refreshed instead of stacking a copy.
+  pub fn push(&self, severity: Severity, message: impl Into<Arc<str>>) {
+    let message = message.into();
+    let expires = Instant::now() + Self::TIMEOUT;
+    let mut items = self.items.lock().unwrap();
+    items.retain(|toast| toast.expires > Instant::now());
+
+    match items.iter_mut().find(|toast| toast.severity == severity && toast.message == message) {
+      Some(toast) => toast.expires = expires,
+      None => items.push_back(Toast { severity, message, expires }),
+    }
+    while items.len() > Self::MAX {
+      items.pop_front();
+    }
+  }
+
+  pub fn info(&self, message: impl Into<Arc<str>>) {
+    self.push(Severity::Info, message);
+  }
+
+  pub fn warn(&self, message: impl Into<Arc<str>>) {
+    self.push(Severity::Warning, message);
+  }
+
+  pub fn error(&self, message: impl Into<Arc<str>>) {
+    self.push(Severity::Error, message);
+  }
+
+  fn visible(&self) -> Vec<Toast> {
+    let now = Instant::now();
+    self.items.lock().unwrap().iter().filter(|toast| toast.expires > now).cloned().collect()
+  }
+}
+
+impl Widget for &Toasts {
+  /// `area` is the whole screen, toasts only cover its top right corner.
+  fn render(self, area: Rect, buf: &mut Buffer)
+  where
+    Self: Sized,
+  {
+    let toasts = self.visible();
+    if toasts.is_empty() {
+      return;
+    }
+
+    let [_, column] = Layout::horizontal([Constraint::Fill(1), Constraint::Length(Toasts::WIDTH)]).areas(area);
+    let slots = Layout::vertical(toasts.iter().map(|_| Constraint::Length(Toasts::HEIGHT))).split(column);
+
+    // Newest on top.
+    for (toast, slot) in toasts.iter().rev().zip(slots.iter()) {
+      clear(*slot, buf);
+      let block = blk()
+        .title_top(Line::raw(format!(" {} {} ", toast.severity.icon(), toast.severity)).left_aligned())
+        .border_style(toast.severity.color());
+      Paragraph::new(toast.message.as_ref())
+        .fg(Color::White)
+        .wrap(Wrap { trim: true })
+        .block(block)
+        .render(*slot, buf);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_push_dedupes() {
+    let toasts = Toasts::default();
+    toasts.warn("Rate limited");
+    toasts.warn("Rate limited");
+    toasts.error("Rate limited");
+    assert_eq!(toasts.visible().len(), 2);
+
+    for index in 0..10 {
+      toasts.info(format!("message {index}"));
+    }
+    let visible = toasts.visible();
+    assert_eq!(visible.len(), Toasts::MAX);
+    assert_eq!(visible.last().unwrap().message.as_ref(), "message 9");
+  }
+}
@@ -0,0 +1,457 @@
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crossterm::event::{KeyEvent, MouseEvent, MouseEventKind};
+use helper::{UnhandledEvent, keys};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Position, Rect};
+use ratatui::prelude::{StatefulWidget, Stylize, Widget};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Cell, Row, Table, TableState};
+use strum::{Display, VariantArray};
+use tokio::sync::RwLock;
+
+use crate::app::Export;
+use crate::app::handler::BucketStatus;
+use crate::areas::KnownArea;
+use crate::config::Action;
+use crate::mouse_area;
+use crate::ui::{blk, clear, colors};
+
+/// Every finished check, shown as a sortable table.
+#[derive(Debug, Default)]
+pub struct Results {
+  inner: Arc<RwLock<Inner>>,
+  state: Arc<RwLock<TableState>>,
+  known_area: KnownArea,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+  /// Arrival order, the oldest go once there are more than `Results::CAPACITY`.
+  items: VecDeque<BucketStatus>,
+  /// Arrival number of `items[0]`.
+  first: usize,
+  /// Arrival numbers in display order.
+  order: Vec<usize>,
+  sort: Option<Sort>,
+  hidden: Vec<Column>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Sort {
+  pub column: Column,
+  pub descending: bool,
+}
+
+impl Sort {
+  fn compare(&self, a: &BucketStatus, b: &BucketStatus) -> Ordering {
+    let ordering = self.column.compare(a, b);
+    if self.descending { ordering.reverse() } else { ordering }
+  }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Display, VariantArray)]
+pub enum Column {
+  Name,
+  Status,
+  Region,
+  Code,
+  Latency,
+  #[strum(to_string = "Checked")]
+  CheckDate,
+}
+
+impl Column {
+  fn width(&self) -> Constraint {
+    match self {
+      Column::Name => Constraint::Fill(1),
+      Column::Status => Constraint::Length(16),
+      Column::Region => Constraint::Length(16),
+      Column::Code => Constraint::Length(24),
+      Column::Latency => Constraint::Length(10),
+      Column::CheckDate => Constraint::Length(10),
+    }
+  }
+
+  fn compare(&self, a: &BucketStatus, b: &BucketStatus) -> Ordering {
+    match self {
+      Column::Name => a.name().cmp(b.name()),
+      Column::Status => a.status().cmp(&b.status()),
+      Column::Region => a.region().to_string().cmp(&b.region().to_string()),
+      Column::Code => (a.code(), a.error_code()).cmp(&(b.code(), b.error_code())),
+      Column::Latency => a.timings().total.cmp(&b.timings().total),
+      Column::CheckDate => a.timestamp().cmp(&b.timestamp()),
+    }
+  }
+
+  /// Unlike the table cells, timestamps keep their date and timezone.
+  fn value(&self, bucket: &BucketStatus) -> String {
+    match self {
+      Column::Name => bucket.name().to_string(),
+      Column::Status => bucket.status().to_string(),
+      Column::Region => bucket.region().to_string(),
+      Column::Code => {
+        let code = bucket.code().map(|code| code.as_u16().to_string()).unwrap_or_else(|| String::from("-"));
+        match bucket.error_code() {
+          Some(error_code) => format!("{code} {error_code}"),
+          None => code,
+        }
+      }
+      Column::Latency => format!("{}ms", bucket.timings().total.as_millis()),
+      Column::CheckDate => bucket.timestamp().to_rfc3339(),
+    }
+  }
+
+  fn cell(&self, bucket: &BucketStatus) -> Cell<'static> {
+    match self {
+      Column::Name => Cell::new(bucket.name().to_string()).fg(bucket.status().color()),
+      Column::Status => Cell::new(format!("{} {}", bucket.status().icon(), bucket.status())),
+      Column::Region => Cell::new(bucket.region().to_string()),
+      Column::Code | Column::Latency => Cell::new(self.value(bucket)),
+      Column::CheckDate => Cell::new(bucket.timestamp().format("%H:%M:%S").to_string()).fg(Color::DarkGray),
+    }
+  }
+}
+
+impl Inner {
+  fn item(&self, arrival: usize) -> &BucketStatus {
+    &self.items[arrival - self.first]
+  }
+
+  /// Items in display order.
+  fn ordered(&self) -> impl Iterator<Item = &BucketStatus> {
+    self.order.iter().map(|arrival| self.item(*arrival))
+  }
+
+  fn resort(&mut self) {
+    let mut order = (self.first..self.first + self.items.len()).collect::<Vec<_>>();
+    if let Some(sort) = self.sort {
+      order.sort_by(|a, b| sort.compare(self.item(*a), self.item(*b)));
+    }
+    self.order = order;
+  }
+
+  fn columns(&self) -> Vec<Column> {
+    Column::VARIANTS.iter().filter(|column| !self.hidden.contains(column)).copied().collect()
+  }
+
+  fn selected(&self, state: &TableState) -> Option<&BucketStatus> {
+    Some(self.item(*self.order.get(state.selected()?)?))
+  }
+}
+
+impl Results {
+  /// Checks kept for the table, every one of them is in the output file anyway.
+  pub const CAPACITY: usize = 10_000;
+
+  pub async fn add(&self, bucket: BucketStatus) {
+    let mut inner = self.inner.write().await;
+    let arrival = inner.first + inner.items.len();
+    let position = match inner.sort {
+      Some(sort) => inner.order.partition_point(|i| sort.compare(inner.item(*i), &bucket).is_le()),
+      None => inner.order.len(),
+    };
+    inner.items.push_back(bucket);
+    inner.order.insert(position, arrival);
+    // The selection follows its row as others come and go around it.
+    let mut state = self.state.write().await;
+    if let Some(selected) = state.selected().filter(|selected| *selected >= position) {
+      state.select(Some(selected.saturating_add(1)));
+    }
+
+    if inner.items.len() > Self::CAPACITY {
+      inner.items.pop_front();
+      let oldest = inner.first;
+      inner.first += 1;
+      if let Some(position) = inner.order.iter().position(|arrival| *arrival == oldest) {
+        inner.order.remove(position);
+        if let Some(selected) = state.selected().filter(|selected| *selected > position) {
+          state.select(Some(selected - 1));
+        }
+      }
+    }
+  }
+
+  /// Names of every available bucket, in display order.
+  pub async fn available_names(&self) -> Vec<String> {
+    let inner = self.inner.read().await;
+    inner
+      .ordered()
+      .filter(|bucket| bucket.status().is_available())
+      .map(|bucket| bucket.name().to_string())
+      .collect()
+  }
+
+  /// Visible columns in display order.
+  pub async fn export(&self) -> Export {
+    let inner = self.inner.read().await;
+    let columns = inner.columns();
+    let rows = inner
+      .ordered()
+      .map(|bucket| columns.iter().map(|column| column.value(bucket)).collect())
+      .collect();
+
+    Export {
+      columns: columns.iter().map(Column::to_string).collect(),
+      rows,
+    }
+  }
+
+  pub async fn reset(&self) {
+    let mut inner = self.inner.write().await;
+    inner.first += inner.items.len();
+    inner.items.clear();
+    inner.order.clear();
+    self.state.write().await.select(None);
+  }
+
+  pub async fn selected(&self) -> Option<BucketStatus> {
+    let inner = self.inner.read().await;
+    let state = self.state.read().await;
+    inner.selected(&state).cloned()
+  }
+
+  /// First press sorts ascending, the next ones flip the direction.
+  pub async fn sort_by(&self, column: Column) {
+    let mut inner = self.inner.write().await;
+    inner.sort = match inner.sort {
+      Some(sort) if sort.column == column => Some(Sort {
+        descending: !sort.descending,
+        ..sort
+      }),
+      _ => Some(Sort { column, descending: false }),
+    };
+    inner.resort();
+  }
+
+  pub async fn unsort(&self) {
+    let mut inner = self.inner.write().await;
+    inner.sort = None;
+    inner.resort();
+  }
+
+  /// The last visible column can't be hidden.
+  pub async fn toggle_column(&self, column: Column) {
+    let mut inner = self.inner.write().await;
+    if let Some(position) = inner.hidden.iter().position(|hidden| *hidden == column) {
+      inner.hidden.remove(position);
+    } else if inner.hidden.len() + 1 < Column::VARIANTS.len() {
+      inner.hidden.push(column);
+    }
+  }
+
+  /// Actions of the results context, see `Keymap`.
+  pub async fn perform(&self, action: Action) -> Option<UnhandledEvent> {
+    match action {
+      Action::ScrollUp => self.state.write().await.scroll_up_by(1),
+      Action::ScrollDown => self.state.write().await.scroll_down_by(1),
+      Action::PageUp => self.state.write().await.scroll_up_by(10),
+      Action::PageDown => self.state.write().await.scroll_down_by(10),
+      Action::Top => self.state.write().await.select_first(),
+      Action::Bottom => self.state.write().await.select_last(),
+      _ => return None,
+    }
+
+    Some(UnhandledEvent::render())
+  }
+
+  /// Digits sort by the column at that position, `0` unsorts and Alt hides it, they aren't rebindable.
+  pub async fn handle_key(&self, key: KeyEvent) -> Option<UnhandledEvent> {
+    match key {
+      keys!(Char('0'), NONE, Press) => self.unsort().await,
+      keys!(Char(digit), NONE, Press) => self.sort_by(Self::column_key(digit)?).await,
+      keys!(Char(digit), ALT, Press) => self.toggle_column(Self::column_key(digit)?).await,
+      _ => return None,
+    }
+
+    Some(UnhandledEvent::render())
+  }
+
+  fn column_key(digit: char) -> Option<Column> {
+    let index = digit.to_digit(10)?.checked_sub(1)?;
+    Column::VARIANTS.get(index as usize).copied()
+  }
+
+  /// The digits `column_key` takes.
+  fn column_digits() -> String {
+    format!("1-{}", Column::VARIANTS.len())
+  }
+
+  /// What `handle_key` does, for the help.
+  pub fn fixed_keys() -> Vec<(String, &'static str)> {
+    vec![
+      (Self::column_digits(), "Sort by column, 0 unsorts"),
+      (format!("alt+{}", Self::column_digits()), "Hide or show a column"),
+    ]
+  }
+
+  pub async fn handle_mouse(&self, mouse_event: MouseEvent) -> Option<UnhandledEvent> {
+    use MouseEventKind::{ScrollDown, ScrollUp};
+
+    if !self.intersects(mouse_area(&mouse_event).as_position()) {
+      return None;
+    }
+
+    match mouse_event.kind {
+      ScrollUp => self.state.write().await.scroll_up_by(1),
+      ScrollDown => self.state.write().await.scroll_down_by(1),
+      _ => return None,
+    }
+
+    Some(UnhandledEvent::render())
+  }
+
+  pub fn intersects(&self, position: Position) -> bool {
+    self.known_area.intersects(position)
+  }
+
+  pub fn render_focused(&self, area: Rect, buf: &mut Buffer, focused: bool) {
+    self.known_area.replace(area);
+    clear(area, buf);
+
+    let inner = self.inner.blocking_read();
+    let columns = inner.columns();
+
+    let header = columns
+      .iter()
+      .map(|column| {
+        let index = Column::VARIANTS.iter().position(|variant| variant == column).unwrap_or_default() + 1;
+        let arrow = match inner.sort {
+          Some(Sort { column: sorted, descending }) if sorted == *column => {
+            if descending {
+              "▼"
+            } else {
+              "▲"
+            }
+          }
+          _ => "",
+        };
+        Cell::new(format!("{index}:{column}{arrow}"))
+      })
+      .collect::<Row>()
+      .style(Style::new().bold().fg(Color::Cyan));
+
+    // Only the rows in view are built, the table can hold `CAPACITY` of them.
+    let mut state = self.state.blocking_write();
+    let (offset, window) = Self::window(&mut state, inner.order.len(), area.height.saturating_sub(3) as usize);
+    let rows = inner.order[window]
+      .iter()
+      .map(|arrival| inner.item(*arrival))
+      .map(|bucket| columns.iter().map(|column| column.cell(bucket)).collect::<Row>());
+
+    let title = Line::raw(format!(" 📋Results ({}) ", inner.items.len())).left_aligned();
+    let controls = Line::from(vec![
+      Span::raw(" "),
+      Span::raw(format!("[{}]", Self::column_digits())).blue(),
+      Span::raw(" Sort "),
+      Span::raw("[0]").blue(),
+      Span::raw(" Unsort "),
+      Span::raw(format!("[ALT+{}]", Self::column_digits())).blue(),
+      Span::raw(" Columns "),
+      Span::raw("[ENTER]").blue(),
+      Span::raw(" Details "),
+    ])
+    .right_aligned();
+    let mut block = blk().title_top(title).title_bottom(controls);
+    if focused {
+      block = block.border_style(colors().focus);
+    }
+
+    let table = Table::new(rows, columns.iter().map(Column::width))
+      .header(header)
+      .block(block)
+      .row_highlight_style(Style::new().reversed());
+
+    let mut visible = TableState::default().with_selected(state.selected().map(|selected| selected - offset));
+    StatefulWidget::render(table, area, buf, &mut visible);
+  }
+
+  /// Clamps the selection to `len` rows and scrolls it into a view of `height`, returning the offset and the rows in view.
+  fn window(state: &mut TableState, len: usize, height: usize) -> (usize, Range<usize>) {
+    let height = height.max(1);
+    let selected = state.selected().filter(|_| len > 0).map(|selected| selected.min(len - 1));
+    state.select(selected);
+
+    let mut offset = state.offset().min(len.saturating_sub(height));
+    match selected {
+      Some(selected) if selected < offset => offset = selected,
+      Some(selected) if selected >= offset + height => offset = selected + 1 - height,
+      _ => {}
+    }
+    *state.offset_mut() = offset;
+    (offset, offset..(offset + height).min(len))
+  }
+}
+
+impl Clone for Results {
+  fn clone(&self) -> Self {
+    Self {
+      inner: Arc::clone(&self.inner),
+      state: Default::default(),
+      known_area: Default::default(),
+    }
+  }
+}
+
+impl Widget for &Results {
+  fn render(self, area: Rect, buf: &mut Buffer) {
+    self.render_focused(area, buf, false);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_column_key() {
+    assert_eq!(Results::column_key('1'), Some(Column::Name));
+    assert_eq!(Results::column_key('6'), Some(Column::CheckDate));
+    assert_eq!(Results::column_key('0'), None);
+    assert_eq!(Results::column_key('7'), None);
+  }
+
+  #[tokio::test]
+  async fn test_capacity() {
+    let results = Results::default();
+    results.sort_by(Column::Name).await;
+    results.sort_by(Column::Name).await;
+    for index in 0..Results::CAPACITY + 2 {
+      results.add(BucketStatus::unchecked(&format!("bucket-{index:05}"))).await;
+    }
+    results.state.write().await.select(Some(0));
+    results.add(BucketStatus::unchecked("bucket-99999")).await;
+
+    let inner = results.inner.read().await;
+    assert_eq!(
+      (inner.items.len(), inner.order.len(), inner.first),
+      (Results::CAPACITY, Results::CAPACITY, 3)
+    );
+    assert_eq!(inner.items[0].name(), "bucket-00003");
+    assert_eq!(inner.ordered().last().map(BucketStatus::name), Some("bucket-00003"));
+    drop(inner);
+    assert_eq!(
+      results.selected().await.map(|bucket| bucket.name().to_string()).as_deref(),
+      Some("bucket-10001")
+    );
+  }
+
+  #[test]
+  fn test_window() {
+    let mut state = TableState::default().with_selected(Some(usize::MAX));
+    assert_eq!(Results::window(&mut state, 100, 10), (90, 90..100));
+    assert_eq!(state.selected(), Some(99));
+
+    state.select(Some(42));
+    assert_eq!(Results::window(&mut state, 100, 10), (42, 42..52));
+    state.select(Some(45));
+    assert_eq!(Results::window(&mut state, 100, 10), (42, 42..52));
+    assert_eq!(Results::window(&mut state, 3, 10), (0, 0..3));
+    assert_eq!(Results::window(&mut state, 0, 10), (0, 0..0));
+    assert_eq!(state.selected(), None);
+  }
+}
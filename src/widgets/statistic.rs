@@ -1,19 +1,92 @@
-use std::sync::Arc;
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::prelude::{Color, Stylize, Widget};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Gauge, Paragraph};
+use ratatui::widgets::{Gauge, Paragraph, Sparkline};
 
+use crate::app::handler::Timings;
+use crate::app::{Clock, Progress, hms};
 use crate::ui::blk;
+use crate::widgets::History;
 
 #[derive(Debug, Clone)]
 pub struct Statistic {
   cur: Arc<AtomicUsize>,
   max: Arc<AtomicUsize>,
   label: String,
+  latencies: Arc<Mutex<Latencies>>,
+  clock: Arc<Mutex<Clock>>,
+  history: History,
+}
+
+/// Picks one phase out of a probe's timings.
+type Phase = fn(&Timings) -> Option<Duration>;
+
+/// Phases shown with their percentiles, in display order.
+const PHASES: [(&str, Phase); 4] = [
+  ("dns", |t| Some(t.dns)),
+  ("connect", |t| t.connect),
+  ("request", |t| Some(t.request)),
+  ("total", |t| Some(t.total)),
+];
+
+/// The last `LATENCY_WINDOW` timings, with each phase's samples kept sorted as they come and go.
+#[derive(Debug, Default)]
+struct Latencies {
+  window: VecDeque<Timings>,
+  sorted: [Vec<Duration>; PHASES.len()],
+}
+
+impl Latencies {
+  /// Only the last `LATENCY_WINDOW` results count toward the percentiles.
+  const LATENCY_WINDOW: usize = 1000;
+
+  fn record(&mut self, timings: Timings) {
+    for ((_, phase), sorted) in PHASES.iter().zip(&mut self.sorted) {
+      if let Some(sample) = phase(&timings) {
+        sorted.insert(sorted.partition_point(|kept| *kept < sample), sample);
+      }
+    }
+    self.window.push_back(timings);
+
+    if self.window.len() > Self::LATENCY_WINDOW {
+      let Some(oldest) = self.window.pop_front() else { return };
+      for ((_, phase), sorted) in PHASES.iter().zip(&mut self.sorted) {
+        if let Some(at) = phase(&oldest).and_then(|sample| sorted.binary_search(&sample).ok()) {
+          sorted.remove(at);
+        }
+      }
+    }
+  }
+}
+
+/// p50, p95 and p99 of one probe phase.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct Percentiles {
+  pub p50: Duration,
+  pub p95: Duration,
+  pub p99: Duration,
+}
+
+impl Percentiles {
+  /// Nearest-rank percentiles of already sorted samples, `None` for an empty sample.
+  pub fn of(samples: &[Duration]) -> Option<Self> {
+    if samples.is_empty() {
+      return None;
+    }
+    let rank = |p: f64| samples[((p * samples.len() as f64).ceil() as usize).clamp(1, samples.len()) - 1];
+
+    Some(Self {
+      p50: rank(0.50),
+      p95: rank(0.95),
+      p99: rank(0.99),
+    })
+  }
 }
 
 impl Default for Statistic {
@@ -28,7 +101,114 @@ impl Statistic {
       cur: Arc::new(AtomicUsize::new(0)),
       max: Arc::new(AtomicUsize::new(100)),
       label: label.into(),
+      latencies: Default::default(),
+      clock: Default::default(),
+      history: Default::default(),
+    }
+  }
+
+  pub fn history(&self) -> &History {
+    &self.history
+  }
+
+  /// New input: the count and the clock start over.
+  pub fn restart(&self) {
+    self.set_current(0);
+    *self.clock.lock().unwrap() = Clock::default();
+  }
+
+  pub fn resume(&self) {
+    self.clock.lock().unwrap().resume();
+  }
+
+  pub fn pause(&self) {
+    self.clock.lock().unwrap().pause();
+  }
+
+  pub fn progress(&self) -> Progress {
+    let clock = self.clock.lock().unwrap();
+    Progress {
+      current: self.get_current(),
+      max: self.get_max(),
+      elapsed: clock.elapsed(),
+      rate: clock.rate(),
+      paused: clock.is_paused(),
+    }
+  }
+
+  pub fn record_timings(&self, timings: Timings) {
+    self.latencies.lock().unwrap().record(timings);
+  }
+
+  /// Of the phase at `index` in `PHASES`.
+  fn percentiles(&self, index: usize) -> Option<Percentiles> {
+    Percentiles::of(&self.latencies.lock().unwrap().sorted[index])
+  }
+
+  /// Rows taken by the title, gauge, history, throughput and latencies.
+  pub const HEIGHT: u16 = 11;
+
+  const HISTORY_LABEL: u16 = 10;
+
+  fn draw_history(&self, area: Rect, buf: &mut Buffer) {
+    let [checks_area, errors_area] = Layout::vertical([Constraint::Length(2); 2]).areas(area);
+    let (checks, errors) = self.history.series(area.width.saturating_sub(Self::HISTORY_LABEL) as usize);
+
+    for (area, label, data, color) in [
+      (checks_area, "checks/s", checks, Color::Green),
+      (errors_area, "errors/s", errors, Color::Red),
+    ] {
+      let [label_area, sparkline_area] = Layout::horizontal([Constraint::Length(Self::HISTORY_LABEL), Constraint::Fill(1)]).areas(area);
+      let last = data.last().copied().unwrap_or_default();
+      let peak = data.iter().copied().max().unwrap_or_default();
+      let lines = vec![
+        Line::from(vec![Span::raw(format!(" {label}")).fg(Color::Cyan)]),
+        Line::from(vec![
+          Span::raw(format!(" {last}")).fg(color),
+          Span::raw(format!(" ^{peak}")).fg(Color::DarkGray),
+        ]),
+      ];
+
+      Paragraph::new(lines).render(label_area, buf);
+      Sparkline::default().data(&data).style(color).render(sparkline_area, buf);
+    }
+  }
+
+  fn draw_throughput(&self) -> Line<'_> {
+    let progress = self.progress();
+    let eta = progress.eta().map(hms).unwrap_or_else(|| String::from("--:--:--"));
+    let mut spans = vec![
+      Span::raw(" "),
+      Span::raw(format!("{:.1}/s", progress.rate)).fg(Color::Yellow),
+      Span::raw(" elapsed ").fg(Color::Cyan),
+      Span::raw(hms(progress.elapsed)),
+      Span::raw(" eta ").fg(Color::Cyan),
+      Span::raw(eta),
+    ];
+    if progress.paused {
+      spans.push(Span::raw(" paused").fg(Color::Red).italic());
     }
+
+    Line::from(spans)
+  }
+
+  fn draw_latencies(&self) -> Vec<Line<'_>> {
+    PHASES
+      .iter()
+      .enumerate()
+      .map(|(index, (name, _))| {
+        let mut spans = vec![Span::raw(format!(" {name:<8}")).fg(Color::Cyan)];
+        match self.percentiles(index) {
+          Some(Percentiles { p50, p95, p99 }) => spans.extend([
+            Span::raw(format!("p50 {:>6} ", millis(p50))),
+            Span::raw(format!("p95 {:>6} ", millis(p95))).fg(Color::Yellow),
+            Span::raw(format!("p99 {:>6}", millis(p99))).fg(Color::Red),
+          ]),
+          None => spans.push(Span::raw("-").fg(Color::DarkGray)),
+        }
+        Line::from(spans)
+      })
+      .collect()
   }
 
   pub fn set_current(&self, value: usize) {
@@ -40,7 +220,12 @@ impl Statistic {
   }
 
   pub fn increment(&self) {
-    self.cur.fetch_add(1, Ordering::Relaxed);
+    let current = self.cur.fetch_add(1, Ordering::Relaxed) + 1;
+    let mut clock = self.clock.lock().unwrap();
+    clock.tick();
+    if current >= self.get_max() {
+      clock.finish();
+    }
   }
 
   pub fn get_current(&self) -> usize {
@@ -61,7 +246,7 @@ impl Statistic {
     self.get_current() >= self.get_max()
   }
 
-  fn draw_title(&self) -> Line {
+  fn draw_title(&self) -> Line<'_> {
     let current = self.get_current();
     let max = self.get_max();
     let percentage = self.get_percentage();
@@ -87,7 +272,14 @@ impl Widget for &Statistic {
   where
     Self: Sized,
   {
-    let [title_area, gauge_area] = Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(area);
+    let [title_area, gauge_area, history_area, throughput_area, latency_area] = Layout::vertical([
+      Constraint::Length(1),
+      Constraint::Length(1),
+      Constraint::Length(4),
+      Constraint::Length(1),
+      Constraint::Length(4),
+    ])
+    .areas(area);
 
     // Render title
     Paragraph::new(self.draw_title()).render(title_area, buf);
@@ -107,5 +299,47 @@ impl Widget for &Statistic {
       .gauge_style(gauge_color)
       .ratio(ratio)
       .render(gauge_area, buf);
+
+    self.draw_history(history_area, buf);
+    Paragraph::new(self.draw_throughput()).render(throughput_area, buf);
+    Paragraph::new(self.draw_latencies()).render(latency_area, buf);
+  }
+}
+
+fn millis(duration: Duration) -> String {
+  format!("{}ms", duration.as_millis())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_percentiles() {
+    assert!(Percentiles::of(&[]).is_none());
+
+    let samples = (1..=100).map(Duration::from_millis).collect::<Vec<_>>();
+    let percentiles = Percentiles::of(&samples).unwrap();
+    assert_eq!(percentiles.p50, Duration::from_millis(50));
+    assert_eq!(percentiles.p95, Duration::from_millis(95));
+    assert_eq!(percentiles.p99, Duration::from_millis(99));
+  }
+
+  #[test]
+  fn test_latency_window() {
+    let mut latencies = Latencies::default();
+    for millis in (0..Latencies::LATENCY_WINDOW as u64 + 10).rev() {
+      latencies.record(Timings {
+        dns: Duration::from_millis(millis),
+        ..Default::default()
+      });
+    }
+
+    let dns = &latencies.sorted[0];
+    assert_eq!(dns.len(), Latencies::LATENCY_WINDOW);
+    assert!(dns.is_sorted());
+    // The ten oldest, and largest, samples left the window.
+    assert_eq!(dns.last(), Some(&Duration::from_millis(Latencies::LATENCY_WINDOW as u64 - 1)));
+    assert!(latencies.sorted[1].is_empty());
   }
 }
@@ -0,0 +1,7 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Tags work a widget hands out to run on its own task, unique for the whole run so popups never mix up results.
+pub fn ticket() -> u64 {
+  static TICKETS: AtomicU64 = AtomicU64::new(0);
+  TICKETS.fetch_add(1, Ordering::Relaxed)
+}
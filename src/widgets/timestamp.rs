@@ -3,6 +3,7 @@ use std::ops::{Deref, DerefMut};
 use chrono::{DateTime, Local};
 use ratatui::prelude::{Color, Stylize};
 use ratatui::text::{Line, Span};
+use serde::{Serialize, Serializer};
 
 impl Default for Timestamp {
   fn default() -> Self {
@@ -54,3 +55,9 @@ impl<'s> From<&'s Timestamp> for Line<'s> {
     Line::from_iter(value.as_spans())
   }
 }
+
+impl Serialize for Timestamp {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&self.0.to_rfc3339())
+  }
+}